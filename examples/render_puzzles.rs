@@ -0,0 +1,33 @@
+//! Exports pictures of the day 4 matches and the day 6 patrol as PNG and SVG files.
+//!
+//! Run with `cargo run --release --example render_puzzles [OUTPUT_DIR]`.
+
+use std::env;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2024::problems::problem4::{self, Problem4};
+use advent_of_code_2024::problems::problem6::{self, Problem6};
+use advent_of_code_2024::render::Image;
+use advent_of_code_2024::{AocError, Solution};
+
+fn save<T: Hash + Eq>(image: &Image<T>, directory: &Path, name: &str) -> Result<(), AocError> {
+    for extension in ["png", "svg"] {
+        let path = directory.join(format!("{}.{}", name, extension));
+        image.save(&path)?;
+        println!("Wrote {} ({}x{})", path.display(), image.width(), image.height());
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), AocError> {
+    let directory = env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+
+    let search_matrix = Problem4::parse(include_str!("../problems/problem4.txt"))?;
+    save(&problem4::words_image(&search_matrix).cell_size(6), &directory, "day4_xmas")?;
+    save(&problem4::crosses_image(&search_matrix).cell_size(6), &directory, "day4_x_mas")?;
+
+    let (map, guard) = Problem6::parse(include_str!("../problems/problem6.txt"))?;
+    save(&problem6::patrol_image(&map, guard).cell_size(6), &directory, "day6_patrol")?;
+    Ok(())
+}
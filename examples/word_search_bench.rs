@@ -0,0 +1,130 @@
+//! Compares the search modes of `WordSearch` on the day 4 input with the candidate stack search
+//! day 4 used before `WordSearch` existed.
+//!
+//! Run with `cargo run --release --example word_search_bench`.
+
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+
+use itertools::Itertools;
+
+use advent_of_code_2024::bench::{self, BenchConfig, Stats};
+use advent_of_code_2024::grid::{Direction, Grid, Pos};
+use advent_of_code_2024::word_search::{SearchConfig, SearchMode, WordSearch};
+
+/// Repeats the rows and columns of `input_string` `times` times.
+fn tile(input_string: &str, times: usize) -> String {
+    let rows = input_string.lines().map(|row| row.repeat(times)).collect::<Vec<_>>();
+    (0..times).flat_map(|_| rows.iter()).join("\n")
+}
+
+/// Partial `XMAS` found by the candidate stack search.
+#[derive(Clone, Eq, PartialEq, Hash)]
+struct Candidate {
+    next_position: Pos,
+    direction: Direction,
+    next_required_char: char,
+    previous_positions: VecDeque<Pos>,
+}
+
+/// Occurrences of `XMAS`, found by following candidates from every `X` one character at a time.
+fn candidate_stack(grid: &Grid<char>) -> Vec<Vec<Pos>> {
+    let mut found_words = Vec::new();
+    let mut initial_candidates = HashSet::new();
+    for (start, _) in grid.iter().filter(|(_, c)| **c == 'X') {
+        for direction in Direction::ALL {
+            if let Some(next_position) = grid.offset(start, direction) {
+                initial_candidates.insert(Candidate {
+                    next_position,
+                    direction,
+                    next_required_char: 'M',
+                    previous_positions: VecDeque::from([start]),
+                });
+            }
+        }
+    }
+
+    let mut candidates = initial_candidates.iter().cloned().collect::<Vec<_>>();
+    while let Some(candidate) = candidates.pop() {
+        if grid[candidate.next_position] != candidate.next_required_char {
+            continue;
+        }
+        let mut previous_positions = candidate.previous_positions;
+        previous_positions.push_back(candidate.next_position);
+        let next_required_char = match candidate.next_required_char {
+            'M' => 'A',
+            'A' => 'S',
+            _ => {
+                found_words.push(Vec::from(previous_positions));
+                continue;
+            }
+        };
+        if let Some(next_position) = grid.offset(candidate.next_position, candidate.direction) {
+            candidates.push(Candidate {
+                next_position,
+                direction: candidate.direction,
+                next_required_char,
+                previous_positions,
+            });
+        }
+    }
+    found_words
+}
+
+fn median(stats: &Stats) -> String {
+    format!("{:?}", Duration::from_nanos(stats.median_ns))
+}
+
+/// Times both modes for `words` and, for `XMAS` only, the candidate stack search.
+fn run(name: &str, grid: &Grid<char>, words: &[String], config: &BenchConfig) {
+    let cells = grid.rows() * grid.cols();
+    let time = |mode: SearchMode| -> (usize, Stats) {
+        let search = WordSearch::new(words, SearchConfig { mode, ..SearchConfig::default() });
+        (search.find(grid).len(), bench::time(config, cells, || search.find(grid)))
+    };
+    let (scan_matches, scan) = time(SearchMode::Scan);
+    let (automaton_matches, automaton) = time(SearchMode::Automaton);
+    assert_eq!(scan_matches, automaton_matches);
+
+    let baseline = (words == ["XMAS"]).then(|| {
+        assert_eq!(candidate_stack(grid).len(), scan_matches);
+        bench::time(config, cells, || candidate_stack(grid))
+    });
+    let speedup = |stats: &Stats| format!("{:.2}x", stats.median_ns as f64 / automaton.median_ns as f64);
+
+    println!(
+        "{:<24}  {:>7}  {:>10}  {:>10}  {:>10}  {:>8}  {:>8}",
+        name,
+        scan_matches,
+        baseline.as_ref().map(median).unwrap_or_else(|| "-".to_string()),
+        median(&scan),
+        median(&automaton),
+        baseline.as_ref().map(speedup).unwrap_or_else(|| "-".to_string()),
+        speedup(&scan)
+    );
+}
+
+fn main() {
+    let input_string = include_str!("../problems/problem4.txt");
+    let config = BenchConfig { warmup: 1, iterations: 5 };
+    let xmas = vec!["XMAS".to_string()];
+    // Every four letter word over the letters of the puzzle, 256 words sharing many prefixes.
+    let all_words = itertools::repeat_n("XMAS".chars(), 4)
+        .multi_cartesian_product()
+        .map(|chars| chars.into_iter().collect::<String>())
+        .collect::<Vec<_>>();
+
+    println!(
+        "{:<24}  {:>7}  {:>10}  {:>10}  {:>10}  {:>8}  {:>8}",
+        "case", "matches", "day 4", "scan", "automaton", "vs day 4", "vs scan"
+    );
+    for times in [1, 2] {
+        let grid = Grid::parse(&tile(input_string, times), |_, c| Ok(c)).unwrap();
+        let size = format!("{}x{}", grid.rows(), grid.cols());
+        run(&format!("XMAS, {}", size), &grid, &xmas, &config);
+        // The scan takes seconds for all words on larger grids.
+        if times == 1 {
+            run(&format!("{} words, {}", all_words.len(), size), &grid, &all_words, &config);
+        }
+    }
+}
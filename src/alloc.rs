@@ -0,0 +1,147 @@
+use std::alloc::{GlobalAlloc, Layout};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+/// Allocation counts of one measured section.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations count as one allocation each.
+    pub allocations: u64,
+    /// Total bytes requested by all allocations.
+    pub bytes: u64,
+    /// Highest number of live bytes above the level at the start of the section.
+    pub peak_bytes: usize,
+}
+
+/// Global allocator wrapper counting the allocations of `A`.
+///
+/// Counting is off until [`CountingAllocator::enable`] is called, so installing the wrapper
+/// costs a single relaxed load per allocation otherwise. The counters are shared by all
+/// threads, allocations of rayon workers are included in the stats of the section.
+pub struct CountingAllocator<A> {
+    inner: A,
+    enabled: AtomicBool,
+    allocations: AtomicU64,
+    bytes: AtomicU64,
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl<A> CountingAllocator<A> {
+    pub const fn new(inner: A) -> Self {
+        CountingAllocator {
+            inner,
+            enabled: AtomicBool::new(false),
+            allocations: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Runs `f` and returns the allocations it made, `None` if counting is disabled.
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+        if !self.is_enabled() {
+            return (f(), None);
+        }
+
+        let start_allocations = self.allocations.load(Ordering::SeqCst);
+        let start_bytes = self.bytes.load(Ordering::SeqCst);
+        let start_current = self.current.load(Ordering::SeqCst);
+        self.peak.store(start_current, Ordering::SeqCst);
+
+        let result = f();
+
+        let stats = AllocStats {
+            allocations: self.allocations.load(Ordering::SeqCst) - start_allocations,
+            bytes: self.bytes.load(Ordering::SeqCst) - start_bytes,
+            peak_bytes: self.peak.load(Ordering::SeqCst).saturating_sub(start_current),
+        };
+        (result, Some(stats))
+    }
+
+    fn record_alloc(&self, size: usize) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(size as u64, Ordering::Relaxed);
+        let current = self.current.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(&self, size: usize) {
+        if !self.is_enabled() {
+            return;
+        }
+        // Memory allocated before counting was enabled must not underflow the live bytes.
+        let _ = self.current.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+            Some(current.saturating_sub(size))
+        });
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.record_dealloc(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.record_dealloc(layout.size());
+            self.record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    use super::{AllocStats, CountingAllocator};
+
+    #[test]
+    fn test_measure() {
+        let allocator = CountingAllocator::new(System);
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        let (_, stats) = allocator.measure(|| unsafe { allocator.dealloc(allocator.alloc(layout), layout) });
+        assert_eq!(stats, None);
+
+        allocator.enable();
+        let (_, stats) = allocator.measure(|| unsafe {
+            let first = allocator.alloc(layout);
+            let second = allocator.realloc(first, layout, 128);
+            allocator.dealloc(second, Layout::from_size_align(128, 8).unwrap());
+            let third = allocator.alloc_zeroed(layout);
+            allocator.dealloc(third, layout);
+        });
+        assert_eq!(stats, Some(AllocStats { allocations: 3, bytes: 256, peak_bytes: 128 }));
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, queue, terminal};
+
+/// Frame rate used when none is given.
+pub const DEFAULT_FPS: u32 = 30;
+
+/// What a key press asks the player to do.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Action {
+    Quit,
+    TogglePause,
+    Step,
+    Ignore,
+}
+
+impl From<KeyEvent> for Action {
+    fn from(key: KeyEvent) -> Self {
+        if key.kind == KeyEventKind::Release {
+            return Action::Ignore;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Char(' ') | KeyCode::Char('p') => Action::TogglePause,
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('n') => Action::Step,
+            _ => Action::Ignore,
+        }
+    }
+}
+
+/// Puts the terminal into raw mode with a hidden cursor and restores it when dropped.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        queue!(out, cursor::Hide, terminal::DisableLineWrap)?;
+        out.flush()?;
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        // Nothing sensible is left to do if restoring the terminal fails.
+        let _ = queue!(out, terminal::EnableLineWrap, cursor::Show);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Plays frames of text in place on the terminal.
+///
+/// While playing, space or `p` pauses and resumes, the right arrow, enter or `n` advance a
+/// single frame while paused and `q`, escape or Ctrl-C stop. Frames taller than the terminal
+/// are cut off at the bottom and lines wider than it are not wrapped.
+#[derive(Debug, Clone)]
+pub struct Player {
+    frame_time: Duration,
+    paused: bool,
+}
+
+impl Player {
+    /// Creates a player showing `fps` frames per second, starting paused if `step` is set.
+    pub fn new(fps: u32, step: bool) -> Self {
+        Player {
+            frame_time: Duration::from_secs(1) / fps.max(1),
+            paused: step,
+        }
+    }
+
+    /// Draws every frame over the previous one until the frames run out or playback is stopped.
+    ///
+    /// The last frame shown stays on the terminal.
+    pub fn play(mut self, frames: impl IntoIterator<Item = String>) -> io::Result<()> {
+        let mut out = io::stdout();
+        let _raw = RawTerminal::enter(&mut out)?;
+        let mut drawn_lines = 0;
+
+        for frame in frames {
+            let shown_at = Instant::now();
+            drawn_lines = draw(&mut out, &frame, drawn_lines)?;
+            if !self.wait(shown_at)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits until the next frame is due, returns `false` if playback was stopped.
+    fn wait(&mut self, shown_at: Instant) -> io::Result<bool> {
+        loop {
+            let event = if self.paused {
+                Some(event::read()?)
+            } else {
+                let remaining = self.frame_time.saturating_sub(shown_at.elapsed());
+                if remaining.is_zero() || !event::poll(remaining)? {
+                    return Ok(true);
+                }
+                Some(event::read()?)
+            };
+
+            let Some(Event::Key(key)) = event else {
+                continue;
+            };
+            match Action::from(key) {
+                Action::Quit => return Ok(false),
+                Action::TogglePause => self.paused = !self.paused,
+                Action::Step if self.paused => return Ok(true),
+                Action::Step | Action::Ignore => {}
+            }
+        }
+    }
+}
+
+/// Draws `frame` over the `drawn_lines` lines of the previous frame and returns its line count.
+fn draw(out: &mut impl Write, frame: &str, drawn_lines: u16) -> io::Result<u16> {
+    // One line is kept free so that the last line does not scroll the terminal, terminals not
+    // reporting a size are assumed to be large enough.
+    let max_lines = match terminal::size() {
+        Ok((_, height)) if height > 1 => height as usize - 1,
+        _ => usize::MAX,
+    };
+    if drawn_lines > 0 {
+        queue!(out, cursor::MoveUp(drawn_lines))?;
+    }
+    queue!(out, cursor::MoveToColumn(0))?;
+
+    let mut lines = 0;
+    for line in frame.lines().take(max_lines) {
+        write!(out, "{}", line)?;
+        queue!(out, terminal::Clear(terminal::ClearType::UntilNewLine))?;
+        write!(out, "\r\n")?;
+        lines += 1;
+    }
+    queue!(out, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+    out.flush()?;
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::Action;
+
+    #[test]
+    fn test_key_actions() {
+        let key = |code: KeyCode| Action::from(KeyEvent::new(code, KeyModifiers::NONE));
+
+        assert_eq!(key(KeyCode::Char('q')), Action::Quit);
+        assert_eq!(Action::from(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)), Action::Quit);
+        assert_eq!(key(KeyCode::Char('c')), Action::Ignore);
+        assert_eq!(key(KeyCode::Char(' ')), Action::TogglePause);
+        assert_eq!(key(KeyCode::Right), Action::Step);
+    }
+}
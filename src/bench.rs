@@ -0,0 +1,208 @@
+use std::convert::Infallible;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::registry::DynSolution;
+use crate::AocError;
+
+/// Stage of a solution that is timed separately.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        }
+    }
+}
+
+/// Summary of the measured runtimes of one stage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    /// Input bytes processed per second based on the median runtime.
+    pub bytes_per_sec: f64,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`, which must not be empty.
+    pub fn from_samples(samples: &[Duration], input_bytes: usize) -> Self {
+        let mut sorted = samples.iter().map(|sample| sample.as_nanos() as u64).collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let median_ns = if sorted.len() % 2 == 0 {
+            (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2
+        } else {
+            sorted[sorted.len() / 2]
+        };
+        let p95_index = (sorted.len() * 95).div_ceil(100).saturating_sub(1);
+        let bytes_per_sec = if median_ns == 0 {
+            f64::INFINITY
+        } else {
+            input_bytes as f64 / (median_ns as f64 / 1e9)
+        };
+
+        Stats {
+            iterations: sorted.len(),
+            min_ns: sorted[0],
+            median_ns,
+            p95_ns: sorted[p95_index],
+            bytes_per_sec,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageReport {
+    pub stage: Stage,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+/// Benchmark results of all stages of one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub day: u8,
+    pub input: String,
+    pub input_bytes: usize,
+    pub stages: Vec<StageReport>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BenchConfig {
+    /// Untimed runs of every stage before measuring.
+    pub warmup: usize,
+    /// Timed runs of every stage, at least one.
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 3,
+            iterations: 100,
+        }
+    }
+}
+
+fn measure<T, E>(config: &BenchConfig, mut f: impl FnMut() -> Result<T, E>) -> Result<Vec<Duration>, E> {
+    for _ in 0..config.warmup {
+        black_box(f()?);
+    }
+
+    let mut samples = Vec::with_capacity(config.iterations);
+    for _ in 0..config.iterations.max(1) {
+        let start = Instant::now();
+        let result = f()?;
+        samples.push(start.elapsed());
+        black_box(result);
+    }
+    Ok(samples)
+}
+
+/// Times `f` on an input of `input_bytes` bytes, for code outside of the registered solutions.
+pub fn time<T>(config: &BenchConfig, input_bytes: usize, mut f: impl FnMut() -> T) -> Stats {
+    let Ok(samples) = measure(config, || Ok::<_, Infallible>(f()));
+    Stats::from_samples(&samples, input_bytes)
+}
+
+/// Times parse, part 1 and part 2 of `solution` on `input_string`.
+///
+/// The parts are timed on a single parsed input so they do not include the parsing time.
+pub fn bench(
+    solution: &dyn DynSolution,
+    input: &str,
+    input_string: &str,
+    config: &BenchConfig,
+) -> Result<BenchReport, AocError> {
+    let input_bytes = input_string.len();
+    let parsed = solution.parse(input_string)?;
+
+    let mut stages = Vec::with_capacity(Stage::ALL.len());
+    for stage in Stage::ALL {
+        let samples = match stage {
+            Stage::Parse => measure(config, || solution.parse(input_string))?,
+            Stage::Part1 => measure(config, || solution.solve(parsed.as_ref(), 1))?,
+            Stage::Part2 => measure(config, || solution.solve(parsed.as_ref(), 2))?,
+        };
+        stages.push(StageReport {
+            stage,
+            stats: Stats::from_samples(&samples, input_bytes),
+        });
+    }
+
+    Ok(BenchReport {
+        day: solution.day(),
+        input: input.to_string(),
+        input_bytes,
+        stages,
+    })
+}
+
+/// Formats a number of bytes with a binary unit prefix.
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Formats a throughput in bytes per second with a binary unit prefix.
+pub fn format_throughput(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{bench, format_throughput, BenchConfig, Stage, Stats};
+    use crate::registry;
+
+    #[test]
+    fn test_stats() {
+        let samples = (1..=20).rev().map(Duration::from_nanos).collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples, 1000);
+
+        assert_eq!(stats.iterations, 20);
+        assert_eq!(stats.min_ns, 1);
+        assert_eq!(stats.median_ns, 10);
+        assert_eq!(stats.p95_ns, 19);
+        assert_eq!(stats.bytes_per_sec, 1e11);
+    }
+
+    #[test]
+    fn test_bench() {
+        let config = BenchConfig { warmup: 0, iterations: 3 };
+        let report = bench(registry::find(1).unwrap(), "<inline>", "3   4\n4   3\n", &config).unwrap();
+
+        assert_eq!(report.input_bytes, 12);
+        assert_eq!(report.stages.iter().map(|stage| stage.stage).collect::<Vec<_>>(), Stage::ALL);
+        assert!(report.stages.iter().all(|stage| stage.stats.iterations == 3));
+    }
+
+    #[test]
+    fn test_format_throughput() {
+        assert_eq!(format_throughput(512.0), "512.0 B/s");
+        assert_eq!(format_throughput(3.5 * 1024.0 * 1024.0), "3.5 MiB/s");
+    }
+}
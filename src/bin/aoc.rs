@@ -0,0 +1,617 @@
+use std::any::Any;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+use clap::error::ErrorKind;
+use clap::{ArgAction, Args, CommandFactory, Parser, Subcommand};
+use itertools::Itertools;
+use log::{error, warn};
+use mimalloc::MiMalloc;
+
+use advent_of_code_2024::{AocError, InputSource, Solution};
+use advent_of_code_2024::alloc::{AllocStats, CountingAllocator};
+use advent_of_code_2024::animation::{self, Player};
+use advent_of_code_2024::bench::{self, format_bytes, format_throughput, BenchConfig, BenchReport, Stage};
+use advent_of_code_2024::logging::{self, LogFilter};
+use advent_of_code_2024::output::{self, AnswerRecord, Format};
+use advent_of_code_2024::history::{self, Comparison, Environment, HistoryRecord};
+use advent_of_code_2024::problems::problem5::{self, GraphFormat, Manual, Problem5, RuleAnalysis};
+use advent_of_code_2024::problems::problem6::{self, Problem6};
+use advent_of_code_2024::registry::{self, DynSolution};
+use advent_of_code_2024::verify::{self, Manifest, Status};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator<MiMalloc> = CountingAllocator::new(MiMalloc);
+
+/// Runner for the Advent of Code 2024 solutions
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log more, repeat for debug and trace output
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+    /// Log less, repeat to only log errors or nothing at all
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "verbose")]
+    quiet: u8,
+    /// Per-module log filter such as `warn,day5=trace`
+    #[arg(long, global = true, env = "RUST_LOG", value_name = "FILTER")]
+    log: Option<LogFilter>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one day or all registered days
+    Run(RunArgs),
+    /// Check every registered solution against the inputs with known answers
+    Verify(VerifyArgs),
+    /// Time parsing and both parts of one day or all registered days
+    Bench(BenchArgs),
+    /// Check the day 5 page ordering rules for cycles, redundant rules and ambiguous updates
+    Rules(RulesArgs),
+}
+
+/// Selection of the days and the input shared by all commands solving puzzles.
+#[derive(Args, Debug)]
+struct SelectionArgs {
+    /// Day of the puzzle
+    #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+    /// Puzzle input file, `-` for stdin or a directory with `problem<DAY>.txt` files
+    #[arg(short, long, default_value = "problems")]
+    input: InputSource,
+    /// Puzzle input given directly on the command line
+    #[arg(long, conflicts_with_all = ["input", "all"])]
+    inline: Option<String>,
+    /// Select every registered day, requires a directory as input
+    #[arg(short, long)]
+    all: bool,
+}
+
+impl SelectionArgs {
+    /// Resolves the selected solutions and their input source, logging why the selection is invalid.
+    fn resolve(self) -> Option<(Vec<&'static dyn DynSolution>, InputSource)> {
+        let solutions = match self.day {
+            Some(day) => registry::find(day).into_iter().collect::<Vec<_>>(),
+            None => registry::solutions().to_vec(),
+        };
+
+        if solutions.is_empty() {
+            error!("No solution registered for the requested day");
+            return None;
+        }
+
+        let source = match self.inline {
+            Some(input_string) => InputSource::Inline(input_string),
+            None => self.input,
+        };
+        if solutions.len() > 1 && !source.is_directory() {
+            error!("Input '{}' is not a directory and cannot be used for several days", source);
+            return None;
+        }
+        Some((solutions, source))
+    }
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: SelectionArgs,
+    /// Part of the puzzle to solve, both parts if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Count allocations, allocated bytes and peak live bytes of every stage, text format only
+    #[arg(long)]
+    alloc_stats: bool,
+    /// Output format of the answers
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Explain which rules every update in the wrong order breaks, day 5 and text format only
+    #[arg(long)]
+    explain: bool,
+    /// Replay the solution in the terminal instead of printing the answers, day 6 only
+    #[arg(long, conflicts_with_all = ["all", "part", "alloc_stats"])]
+    animate: bool,
+    /// Frames per second of the animation
+    #[arg(
+        long,
+        default_value_t = animation::DEFAULT_FPS,
+        value_parser = clap::value_parser!(u32).range(1..),
+        requires = "animate"
+    )]
+    fps: u32,
+    /// Start the animation paused and advance it by one frame per key press
+    #[arg(long, requires = "animate")]
+    step: bool,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// Manifest listing the inputs and their expected answers
+    #[arg(short, long, default_value = verify::DEFAULT_MANIFEST)]
+    manifest: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: SelectionArgs,
+    /// Timed runs of every stage
+    #[arg(short = 'n', long, default_value_t = BenchConfig::default().iterations)]
+    iterations: usize,
+    /// Untimed runs of every stage before measuring
+    #[arg(short, long, default_value_t = BenchConfig::default().warmup)]
+    warmup: usize,
+    /// Print the results as JSON
+    #[arg(long)]
+    json: bool,
+    /// File the results are appended to, one JSON record per line
+    #[arg(long, default_value = history::DEFAULT_HISTORY)]
+    history: PathBuf,
+    /// Do not append the results to the history
+    #[arg(long)]
+    no_save: bool,
+    /// Name stored with the results so that later runs can compare against it
+    #[arg(long)]
+    label: Option<String>,
+    /// Compare against the latest results on this machine with this label, commit or git ref
+    #[arg(long, value_name = "BASELINE")]
+    compare: Option<String>,
+    /// Slowdown of the median in percent that counts as a regression
+    #[arg(long, default_value_t = 10.0, requires = "compare")]
+    threshold: f64,
+}
+
+#[derive(Args, Debug)]
+struct RulesArgs {
+    /// Puzzle input file, `-` for stdin or a directory with a `problem5.txt` file
+    #[arg(short, long, default_value = "problems")]
+    input: InputSource,
+    /// List every redundant rule instead of only counting them
+    #[arg(long)]
+    redundant: bool,
+    /// Write the rule graph as Graphviz DOT to this file
+    #[arg(long, value_name = "FILE")]
+    dot: Option<PathBuf>,
+    /// Write the rule graph as Mermaid flowchart to this file
+    #[arg(long, value_name = "FILE")]
+    mermaid: Option<PathBuf>,
+    /// Only export the pages of this update, numbered from 1, and mark the rules it breaks in red
+    #[arg(long, value_name = "N")]
+    update: Option<usize>,
+}
+
+/// Answer of one part and the time spent solving it.
+struct PartResult {
+    part: u8,
+    answer: Result<String, AocError>,
+    duration: Duration,
+}
+
+/// Parsed input, results of all parts of one day and the allocations of every stage if allocation counting is enabled.
+type DayResult = (Box<dyn Any>, Vec<PartResult>, Vec<(Stage, AllocStats)>);
+
+/// Solves `parts` of one day, only reading and parsing the input fail the whole day.
+fn run_day(solution: &dyn DynSolution, parts: &[u8], source: &InputSource) -> Result<DayResult, AocError> {
+    let input_string = source.read(solution.day())?;
+    let mut allocations = Vec::new();
+
+    let (input, stats) = GLOBAL.measure(|| solution.parse(&input_string));
+    allocations.extend(stats.map(|stats| (Stage::Parse, stats)));
+    let input = input?;
+
+    let mut results = Vec::with_capacity(parts.len());
+    for part in parts {
+        let start = Instant::now();
+        let (answer, stats) = GLOBAL.measure(|| solution.solve(input.as_ref(), *part));
+        let duration = start.elapsed();
+        let stage = if *part == 1 { Stage::Part1 } else { Stage::Part2 };
+        allocations.extend(stats.map(|stats| (stage, stats)));
+        results.push(PartResult { part: *part, answer, duration });
+    }
+    Ok((input, results, allocations))
+}
+
+fn print_allocations(day: u8, allocations: &[(Stage, AllocStats)]) {
+    println!("Day {} allocations:", day);
+    for (stage, stats) in allocations {
+        println!(
+            "  {:<6}  {:>10} allocations  {:>12} allocated  {:>12} peak",
+            stage.name(),
+            stats.allocations,
+            format_bytes(stats.bytes as f64),
+            format_bytes(stats.peak_bytes as f64)
+        );
+    }
+}
+
+fn log_error(err: &AocError) {
+    error!("{}", err);
+    let mut source = err.source();
+    while let Some(cause) = source {
+        error!("  caused by: {}", cause);
+        source = cause.source();
+    }
+}
+
+/// Plays the animation of `day` in the terminal.
+fn animate(day: u8, source: &InputSource, player: Player) -> Option<i32> {
+    if day != Problem6::DAY {
+        error!("Day {} has no animation", day);
+        return Some(1);
+    }
+
+    let (map, guard) = match Problem6::parse_source(source) {
+        Ok(input) => input,
+        Err(err) => {
+            log_error(&err);
+            return Some(err.exit_code());
+        }
+    };
+    if let Err(err) = player.play(problem6::animation_frames(&map, guard)) {
+        error!("Could not animate day {}: {}", day, err);
+        return Some(1);
+    }
+    None
+}
+
+/// Runs the requested days and returns the exit code of the first failure.
+fn run(args: RunArgs) -> Option<i32> {
+    let Some((solutions, source)) = args.selection.resolve() else {
+        return Some(1);
+    };
+
+    if args.animate {
+        return animate(solutions[0].day(), &source, Player::new(args.fps, args.step));
+    }
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if args.alloc_stats {
+        GLOBAL.enable();
+    }
+    if args.explain && solutions.iter().all(|solution| solution.day() != Problem5::DAY) {
+        warn!("Only day 5 has explanations");
+    }
+    if args.explain && args.format != Format::Text {
+        warn!("Explanations are only printed with the text format");
+    }
+
+    let mut records = Vec::new();
+    let mut exit_code = None;
+    for solution in solutions {
+        let day = solution.day();
+        let input_path = source.location(day);
+        let record = |part: u8, answer: Option<String>, duration: Duration, error: Option<&AocError>| AnswerRecord {
+            day,
+            part,
+            answer,
+            duration_ns: duration.as_nanos() as u64,
+            input_path: input_path.clone(),
+            error: error.map(AocError::to_string),
+        };
+
+        let mut day_records = Vec::with_capacity(parts.len());
+        let mut day_allocations = Vec::new();
+        let mut day_input = None;
+        match run_day(solution, &parts, &source) {
+            Ok((input, results, allocations)) => {
+                for result in results {
+                    match result.answer {
+                        Ok(answer) => day_records.push(record(result.part, Some(answer), result.duration, None)),
+                        Err(err) => {
+                            error!("Day {} part {} failed", day, result.part);
+                            log_error(&err);
+                            exit_code = exit_code.or(Some(err.exit_code()));
+                            day_records.push(record(result.part, None, result.duration, Some(&err)));
+                        }
+                    }
+                }
+                day_allocations = allocations;
+                day_input = Some(input);
+            }
+            Err(err) => {
+                error!("Day {} failed", day);
+                log_error(&err);
+                exit_code = exit_code.or(Some(err.exit_code()));
+                day_records.extend(parts.iter().map(|part| record(*part, None, Duration::ZERO, Some(&err))));
+            }
+        }
+
+        // Text output is printed as soon as a day is done, the other formats need all records.
+        if args.format == Format::Text {
+            print!("{}", output::render(Format::Text, &day_records));
+            if !day_allocations.is_empty() {
+                print_allocations(day, &day_allocations);
+            }
+            if let Some(manual) = day_input.filter(|_| args.explain).and_then(|input| input.downcast::<Manual>().ok()) {
+                print!("{}", problem5::explain_violations(&manual));
+            }
+        }
+        records.extend(day_records);
+    }
+
+    if args.format != Format::Text {
+        print!("{}", output::render(args.format, &records));
+    }
+    exit_code
+}
+
+/// Prints a table of all checks and returns exit code 1 if any of them failed.
+fn run_verify(args: VerifyArgs) -> Option<i32> {
+    let manifest = match Manifest::load(&args.manifest) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            log_error(&err);
+            return Some(err.exit_code());
+        }
+    };
+    let checks = verify::verify(&manifest);
+
+    let input_width = checks.iter().map(|check| check.input.len()).max().unwrap_or(0).max(5);
+    println!("{:>3}  {:>4}  {:<input_width$}  {:<7}  Details", "Day", "Part", "Input", "Status");
+    for check in &checks {
+        let row = format!(
+            "{:>3}  {:>4}  {:<input_width$}  {:<7}  {}",
+            check.day,
+            check.part,
+            check.input,
+            check.status.to_string(),
+            check.details()
+        );
+        println!("{}", row.trim_end());
+    }
+
+    let count = |predicate: fn(&Status) -> bool| checks.iter().filter(|check| predicate(&check.status)).count();
+    println!(
+        "{} passed, {} failed, {} missing, {} errors",
+        count(|status| *status == Status::Pass),
+        count(|status| matches!(status, Status::Fail { .. })),
+        count(|status| matches!(status, Status::Missing { .. })),
+        count(|status| matches!(status, Status::Error(_))),
+    );
+
+    if checks.iter().any(|check| check.status.is_failure()) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+fn print_bench_report(report: &BenchReport) {
+    println!(
+        "Day {} ({}, {} bytes, {} iterations)",
+        report.day,
+        report.input,
+        report.input_bytes,
+        report.stages.first().map(|stage| stage.stats.iterations).unwrap_or(0)
+    );
+    println!("  {:<6}  {:>12}  {:>12}  {:>12}  {:>14}", "stage", "min", "median", "p95", "throughput");
+    for stage in &report.stages {
+        println!(
+            "  {:<6}  {:>12}  {:>12}  {:>12}  {:>14}",
+            stage.stage.name(),
+            format!("{:?}", Duration::from_nanos(stage.stats.min_ns)),
+            format!("{:?}", Duration::from_nanos(stage.stats.median_ns)),
+            format!("{:?}", Duration::from_nanos(stage.stats.p95_ns)),
+            format_throughput(stage.stats.bytes_per_sec)
+        );
+    }
+}
+
+fn print_comparison(comparison: &Comparison) {
+    println!(
+        "  Day {:>2} {:<6}  {:>12} -> {:>12}  {:>+7.1}%{}",
+        comparison.day,
+        comparison.stage.name(),
+        format!("{:?}", Duration::from_nanos(comparison.baseline_ns)),
+        format!("{:?}", Duration::from_nanos(comparison.current_ns)),
+        comparison.change_pct,
+        if comparison.regressed { "  REGRESSION" } else { "" }
+    );
+}
+
+/// Compares `reports` against the baseline in the history and returns whether any stage regressed
+/// or a day has no baseline to compare against.
+fn compare_reports(
+    reports: &[BenchReport],
+    records: &[HistoryRecord],
+    baseline: &str,
+    environment: &Environment,
+    threshold: f64,
+    print: bool,
+) -> bool {
+    if print {
+        println!("Comparison against '{}' on {} (threshold {}%)", baseline, environment.machine, threshold);
+    }
+    let mut failed = false;
+    for report in reports {
+        let Some(record) = history::find_baseline(records, baseline, &environment.machine, report.day) else {
+            error!("No results for day {} matching '{}' on {}", report.day, baseline, environment.machine);
+            failed = true;
+            continue;
+        };
+        for comparison in history::compare(&record.report, report, threshold) {
+            if print {
+                print_comparison(&comparison);
+            }
+            failed |= comparison.regressed;
+        }
+    }
+    failed
+}
+
+/// Benchmarks the selected days and returns the exit code of the first failure.
+///
+/// If a baseline is given, a regression against it or a day missing from it exits with code 1.
+fn run_bench(args: BenchArgs) -> Option<i32> {
+    let Some((solutions, source)) = args.selection.resolve() else {
+        return Some(1);
+    };
+    let config = BenchConfig {
+        warmup: args.warmup,
+        iterations: args.iterations,
+    };
+    let mut reports = Vec::with_capacity(solutions.len());
+    let mut exit_code = None;
+    for solution in solutions {
+        let report = source
+            .read(solution.day())
+            .and_then(|input_string| bench::bench(solution, &source.location(solution.day()), &input_string, &config));
+        match report {
+            Ok(report) => {
+                if !args.json {
+                    print_bench_report(&report);
+                }
+                reports.push(report);
+            }
+            Err(err) => {
+                error!("Day {} failed", solution.day());
+                log_error(&err);
+                exit_code = exit_code.or(Some(err.exit_code()));
+            }
+        }
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+    }
+
+    let environment = Environment::detect();
+    // The baseline is looked up before saving so that a run never compares against itself.
+    let records = match args.compare.is_some().then(|| history::load(&args.history)).transpose() {
+        Ok(records) => records.unwrap_or_default(),
+        Err(err) => {
+            log_error(&err);
+            return exit_code.or(Some(err.exit_code()));
+        }
+    };
+
+    if !args.no_save {
+        let new_records = reports
+            .iter()
+            .map(|report| HistoryRecord::new(environment.clone(), args.label.clone(), report.clone()))
+            .collect::<Vec<_>>();
+        if let Err(err) = history::append(&args.history, &new_records) {
+            log_error(&err);
+            exit_code = exit_code.or(Some(err.exit_code()));
+        }
+    }
+
+    if let Some(baseline) = &args.compare {
+        if compare_reports(&reports, &records, baseline, &environment, args.threshold, !args.json) {
+            exit_code = exit_code.or(Some(1));
+        }
+    }
+    exit_code
+}
+
+fn print_rule_analysis(analysis: &RuleAnalysis, rule_count: usize, list_redundant: bool) {
+    println!("{} cyclic components", analysis.components.len());
+    for (component, cycle) in analysis.components.iter().zip(&analysis.cycles) {
+        println!("  {} pages: {}", component.len(), component.iter().join(", "));
+        println!("  shortest cycle: {}", cycle.iter().join(" -> "));
+    }
+
+    println!(
+        "{} of {} rules are repeated or implied by other rules, rules within a cycle are not counted",
+        analysis.redundant.len(),
+        rule_count
+    );
+    if list_redundant {
+        for rule in &analysis.redundant {
+            println!("  {}", rule);
+        }
+    }
+
+    let (mut ambiguous, mut cyclic) = (0, 0);
+    for (index, update) in analysis.updates.iter().enumerate() {
+        match update {
+            Ok(order) => {
+                if let Some(ambiguity) = &order.ambiguity {
+                    println!("Update {} is ambiguous: pages {}", index + 1, ambiguity);
+                    ambiguous += 1;
+                }
+            }
+            Err(err) => {
+                println!("Update {} is cyclic: {}", index + 1, err);
+                cyclic += 1;
+            }
+        }
+    }
+    println!(
+        "{} updates, {} with a unique order, {} ambiguous, {} cyclic",
+        analysis.updates.len(),
+        analysis.updates.len() - ambiguous - cyclic,
+        ambiguous,
+        cyclic,
+    );
+}
+
+/// Prints the analysis of the day 5 rules.
+fn run_rules(args: RulesArgs) -> Option<i32> {
+    let manual = match Problem5::parse_source(&args.input) {
+        Ok(manual) => manual,
+        Err(err) => {
+            log_error(&err);
+            return Some(err.exit_code());
+        }
+    };
+    print_rule_analysis(&problem5::analyze_rules(&manual), manual.rules.len(), args.redundant);
+
+    let update = match args.update {
+        Some(number) => match number.checked_sub(1).and_then(|index| manual.updates.get(index)) {
+            Some(update) => Some(update.as_slice()),
+            None => {
+                error!("There is no update {}, the input has {} updates", number, manual.updates.len());
+                return Some(1);
+            }
+        },
+        None => None,
+    };
+    for (path, format) in [(args.dot, GraphFormat::Dot), (args.mermaid, GraphFormat::Mermaid)] {
+        let Some(path) = path else {
+            continue;
+        };
+        if let Err(err) = problem5::save_rule_graph(&manual, update, format, &path) {
+            log_error(&err);
+            return Some(err.exit_code());
+        }
+    }
+    None
+}
+
+fn main() {
+    let cli = Cli::parse();
+    // A plain conflict with `--format` would also reject an explicit `--format text`.
+    if let Command::Run(args) = &cli.command {
+        if args.alloc_stats && args.format != Format::Text {
+            Cli::command()
+                .error(ErrorKind::ArgumentConflict, "--alloc-stats can only be used with the text format")
+                .exit();
+        }
+        if args.animate && args.format != Format::Text {
+            Cli::command()
+                .error(ErrorKind::ArgumentConflict, "--animate can only be used with the text format")
+                .exit();
+        }
+    }
+    logging::init(&cli.log.unwrap_or_default(), cli.verbose, cli.quiet).unwrap();
+
+    let exit_code = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => run_verify(args),
+        Command::Bench(args) => run_bench(args),
+        Command::Rules(args) => run_rules(args),
+    };
+
+    if let Some(exit_code) = exit_code {
+        exit(exit_code);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+
+use crate::ParseError;
+
+/// Errors surfaced by the library and the `aoc` runner.
+///
+/// Every variant maps to a distinct process exit code so that scripts can tell a broken
+/// input apart from a puzzle that has no answer:
+///
+/// | Variant        | Exit code |
+/// |----------------|-----------|
+/// | `Io`           | 3         |
+/// | `Canonicalize` | 4         |
+/// | `NotFound`     | 5         |
+/// | `Parse`        | 6         |
+/// | `Regex`        | 7         |
+/// | `Unsolvable`   | 8         |
+/// | `Write`        | 9         |
+///
+/// Exit code 1 is left for generic failures and 2 is used by clap for invalid arguments.
+#[derive(Debug)]
+pub enum AocError {
+    /// The input could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The input path could not be resolved to an absolute path.
+    Canonicalize { path: PathBuf, source: io::Error },
+    /// The input path does not exist.
+    NotFound { path: PathBuf },
+    /// The input is malformed, `line` and `column` are 1-based.
+    Parse { line: usize, column: usize, reason: String },
+    /// A regular expression used by a solution failed to compile.
+    Regex(regex::Error),
+    /// The input is well-formed but the puzzle has no answer for it.
+    Unsolvable { day: u8, part: u8, reason: String },
+    /// An output file, e.g. a rendered image or the benchmark history, could not be written.
+    Write { path: PathBuf, source: io::Error },
+}
+
+impl AocError {
+    /// Process exit code reported by the runner for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocError::Io { .. } => 3,
+            AocError::Canonicalize { .. } => 4,
+            AocError::NotFound { .. } => 5,
+            AocError::Parse { .. } => 6,
+            AocError::Regex(_) => 7,
+            AocError::Unsolvable { .. } => 8,
+            AocError::Write { .. } => 9,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io { path, .. } => write!(f, "Could not read input '{}'", path.display()),
+            AocError::Canonicalize { path, .. } => {
+                write!(f, "Cannot canonicalize input path '{}'", path.display())
+            }
+            AocError::NotFound { path } => write!(f, "Input '{}' does not exist", path.display()),
+            AocError::Parse { line, column, reason } => {
+                write!(f, "Invalid input at {}:{}: {}", line, column, reason)
+            }
+            AocError::Regex(_) => write!(f, "Could not compile regex"),
+            AocError::Unsolvable { day, part, reason } => {
+                write!(f, "Day {} part {} has no solution: {}", day, part, reason)
+            }
+            AocError::Write { path, .. } => write!(f, "Could not write output '{}'", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } | AocError::Canonicalize { source, .. } | AocError::Write { source, .. } => {
+                Some(source)
+            }
+            AocError::Regex(source) => Some(source),
+            AocError::NotFound { .. } | AocError::Parse { .. } | AocError::Unsolvable { .. } => None,
+        }
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(err: ParseError) -> Self {
+        AocError::Parse {
+            line: err.line,
+            column: err.column,
+            reason: err.reason,
+        }
+    }
+}
+
+impl From<regex::Error> for AocError {
+    fn from(err: regex::Error) -> Self {
+        AocError::Regex(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::io;
+    use std::path::PathBuf;
+
+    use super::AocError;
+    use crate::ParseError;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            AocError::Io { path: PathBuf::from("a"), source: io::Error::other("io") },
+            AocError::Canonicalize { path: PathBuf::from("a"), source: io::Error::other("io") },
+            AocError::NotFound { path: PathBuf::from("a") },
+            AocError::from(ParseError::new(1, 2, "bad")),
+            AocError::from(regex::Error::Syntax("unclosed group".to_string())),
+            AocError::Unsolvable { day: 5, part: 2, reason: "cycle".to_string() },
+            AocError::Write { path: PathBuf::from("a"), source: io::Error::other("io") },
+        ];
+        let codes = errors.iter().map(AocError::exit_code).collect::<Vec<_>>();
+        assert_eq!(codes, vec![3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_source_chain() {
+        let err = AocError::Io { path: PathBuf::from("a"), source: io::Error::other("disk") };
+        assert_eq!(err.source().unwrap().to_string(), "disk");
+        assert!(AocError::NotFound { path: PathBuf::from("a") }.source().is_none());
+    }
+}
@@ -0,0 +1,372 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Index, IndexMut, Mul, Neg};
+
+use ndarray::Array2;
+
+use crate::ParseError;
+
+/// Position of a cell, `row` and `col` are 0-based.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Pos { row, col }
+    }
+
+    /// Position `dir` away from this one, `None` if a coordinate would become negative.
+    pub fn offset(self, dir: impl Into<Dir>) -> Option<Pos> {
+        let dir = dir.into();
+        Some(Pos {
+            row: self.row.checked_add_signed(dir.d_row)?,
+            col: self.col.checked_add_signed(dir.d_col)?,
+        })
+    }
+
+    /// Offset leading from this position to `other`.
+    pub fn dir_to(self, other: Pos) -> Dir {
+        Dir::new(
+            other.row as isize - self.row as isize,
+            other.col as isize - self.col as isize,
+        )
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Pos { row, col }
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// Offset between two positions, rows grow downwards and columns to the right.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Dir {
+    pub d_row: isize,
+    pub d_col: isize,
+}
+
+impl Dir {
+    pub const fn new(d_row: isize, d_col: isize) -> Self {
+        Dir { d_row, d_col }
+    }
+}
+
+impl Add for Dir {
+    type Output = Dir;
+
+    fn add(self, rhs: Dir) -> Self::Output {
+        Dir::new(self.d_row + rhs.d_row, self.d_col + rhs.d_col)
+    }
+}
+
+impl Mul<isize> for Dir {
+    type Output = Dir;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Dir::new(self.d_row * rhs, self.d_col * rhs)
+    }
+}
+
+impl Neg for Dir {
+    type Output = Dir;
+
+    fn neg(self) -> Self::Output {
+        Dir::new(-self.d_row, -self.d_col)
+    }
+}
+
+/// One step to a neighboring cell, the first four variants are the 4-connected directions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+}
+
+impl Direction {
+    /// Directions of 4-connected neighbors in clockwise order starting at `Up`.
+    pub const ORTHOGONAL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+    pub const DIAGONAL: [Direction; 4] =
+        [Direction::UpRight, Direction::DownRight, Direction::DownLeft, Direction::UpLeft];
+    /// Directions of 8-connected neighbors in clockwise order starting at `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Iterates over the 4-connected directions, or all 8 if `diagonal` is set.
+    pub fn iter(diagonal: bool) -> impl Iterator<Item = Direction> {
+        let directions: &[Direction] = if diagonal { &Self::ALL } else { &Self::ORTHOGONAL };
+        directions.iter().copied()
+    }
+
+    pub fn delta(self) -> Dir {
+        match self {
+            Direction::Up => Dir::new(-1, 0),
+            Direction::Right => Dir::new(0, 1),
+            Direction::Down => Dir::new(1, 0),
+            Direction::Left => Dir::new(0, -1),
+            Direction::UpRight => Dir::new(-1, 1),
+            Direction::DownRight => Dir::new(1, 1),
+            Direction::DownLeft => Dir::new(1, -1),
+            Direction::UpLeft => Dir::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Self::DIAGONAL.contains(&self)
+    }
+
+    /// Rotates clockwise in steps of 45 degrees, negative steps rotate counterclockwise.
+    pub fn rotate(self, steps: i32) -> Direction {
+        let index = Self::ALL.iter().position(|direction| *direction == self).unwrap() as i32;
+        Self::ALL[(index + steps).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn rotate_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Turns 90 degrees counterclockwise.
+    pub fn rotate_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+impl From<Direction> for Dir {
+    fn from(direction: Direction) -> Self {
+        direction.delta()
+    }
+}
+
+/// Rectangular grid of cells, usually parsed from a puzzle input with one character per cell.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses one cell per character with `parse_cell`, every line is a row.
+    ///
+    /// All rows must have the same length, trailing empty lines are ignored. Errors returned by
+    /// `parse_cell` are reported at the position of the character.
+    pub fn parse(
+        input_string: &str,
+        mut parse_cell: impl FnMut(Pos, char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = input_string.lines().collect::<Vec<_>>();
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        let Some(first_row) = rows.first() else {
+            return Err(ParseError::new(1, 1, "Grid is empty"));
+        };
+
+        let no_columns = first_row.chars().count();
+        let mut cells = Vec::with_capacity(rows.len() * no_columns);
+        for (row_index, row) in rows.iter().enumerate() {
+            let row_length = row.chars().count();
+            if row_length != no_columns {
+                return Err(ParseError::new(
+                    row_index + 1,
+                    row_length.min(no_columns) + 1,
+                    format!("Row has {} columns, expected {}", row_length, no_columns),
+                ));
+            }
+            for (column_index, c) in row.chars().enumerate() {
+                let cell = parse_cell(Pos::new(row_index, column_index), c)
+                    .map_err(|reason| ParseError::new(row_index + 1, column_index + 1, reason))?;
+                cells.push(cell);
+            }
+        }
+
+        let cells = Array2::from_shape_vec((rows.len(), no_columns), cells)
+            .expect("Every row was checked to have the same length");
+        Ok(Grid { cells })
+    }
+
+    pub fn from_array(cells: Array2<T>) -> Self {
+        Grid { cells }
+    }
+
+    pub fn as_array(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.rows() && pos.col < self.cols()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get((pos.row, pos.col))
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut((pos.row, pos.col))
+    }
+
+    /// Position `dir` away from `pos`, `None` if it lies outside of the grid.
+    pub fn offset(&self, pos: Pos, dir: impl Into<Dir>) -> Option<Pos> {
+        pos.offset(dir).filter(|next| self.contains(*next))
+    }
+
+    /// Position `dir` away from `pos`, leaving the grid on one side enters it on the opposite side.
+    pub fn offset_wrapping(&self, pos: Pos, dir: impl Into<Dir>) -> Pos {
+        let dir = dir.into();
+        let wrap = |value: usize, delta: isize, len: usize| (value as isize + delta).rem_euclid(len as isize) as usize;
+        Pos::new(wrap(pos.row, dir.d_row, self.rows()), wrap(pos.col, dir.d_col, self.cols()))
+    }
+
+    /// Cell `dir` away from `pos`, `None` if it lies outside of the grid.
+    pub fn get_offset(&self, pos: Pos, dir: impl Into<Dir>) -> Option<&T> {
+        self.get(self.offset(pos, dir)?)
+    }
+
+    /// Neighbors of `pos` in `directions` that lie inside the grid.
+    pub fn neighbors(
+        &self,
+        pos: Pos,
+        directions: impl IntoIterator<Item = Direction>,
+    ) -> impl Iterator<Item = (Direction, Pos, &T)> {
+        directions
+            .into_iter()
+            .filter_map(move |direction| self.offset(pos, direction).map(|next| (direction, next)))
+            .map(|(direction, next)| (direction, next, &self[next]))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.indexed_iter().map(|(index, cell)| (Pos::from(index), cell))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.map(f) }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn from_elem(rows: usize, cols: usize, elem: T) -> Self {
+        Grid { cells: Array2::from_elem((rows, cols), elem) }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        &self.cells[[pos.row, pos.col]]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        &mut self.cells[[pos.row, pos.col]]
+    }
+}
+
+/// Writes one line per row without separators between the cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dir, Direction, Grid, Pos};
+    use crate::ParseError;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("ab\ncd\n\n", |_, c| Ok(c)).unwrap();
+
+        assert_eq!((grid.rows(), grid.cols()), (2, 2));
+        assert_eq!(grid[Pos::new(1, 0)], 'c');
+        assert_eq!(grid.to_string(), "ab\ncd\n");
+
+        assert_eq!(Grid::parse("", |_, c| Ok(c)).unwrap_err(), ParseError::new(1, 1, "Grid is empty"));
+        assert_eq!(
+            Grid::parse("abc\nab\n", |_, c| Ok(c)).unwrap_err(),
+            ParseError::new(2, 3, "Row has 2 columns, expected 3")
+        );
+        assert_eq!(
+            Grid::parse("abc\nabcd\n", |_, c| Ok(c)).unwrap_err(),
+            ParseError::new(2, 4, "Row has 4 columns, expected 3")
+        );
+        assert_eq!(
+            Grid::parse("#.\n.x\n", |_, c| if c == 'x' { Err("Invalid".to_string()) } else { Ok(c == '#') })
+                .unwrap_err(),
+            ParseError::new(2, 2, "Invalid")
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        let grid = Grid::parse("abc\ndef\n", |_, c| Ok(c)).unwrap();
+        let corner = Pos::new(0, 0);
+
+        assert_eq!(grid.offset(corner, Dir::new(-1, 0)), None);
+        assert_eq!(grid.offset(corner, Dir::new(1, 1)), Some(Pos::new(1, 1)));
+        assert_eq!(grid.get_offset(corner, Dir::new(0, 1) * 2), Some(&'c'));
+        assert_eq!(grid.get_offset(corner, Dir::new(2, 0)), None);
+        assert_eq!(corner.dir_to(Pos::new(1, 2)), Dir::new(1, 2));
+
+        let neighbors = grid.neighbors(corner, Direction::ORTHOGONAL).map(|(_, _, c)| *c).collect::<Vec<_>>();
+        assert_eq!(neighbors, ['b', 'd']);
+        assert_eq!(grid.neighbors(Pos::new(1, 1), Direction::iter(true)).count(), 5);
+        assert_eq!(grid.offset(Pos::new(1, 2), Direction::Right), None);
+        assert_eq!(grid.offset(Pos::new(1, 2), Direction::UpLeft), Some(Pos::new(0, 1)));
+        assert_eq!(grid.offset_wrapping(Pos::new(1, 2), Direction::DownRight), Pos::new(0, 0));
+        assert_eq!(grid.offset_wrapping(corner, Dir::new(-3, -4)), Pos::new(1, 2));
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::Up.rotate_right(), Direction::Right);
+        assert_eq!(Direction::Up.rotate_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.rotate(1), Direction::Up);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+        assert!(Direction::ALL.iter().all(|d| d.delta() == -d.opposite().delta()));
+        assert_eq!(Direction::iter(false).filter(|d| d.is_diagonal()).count(), 0);
+        assert_eq!(Dir::from(Direction::Left) * 3, Dir::new(0, -3));
+    }
+}
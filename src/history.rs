@@ -0,0 +1,243 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bench::{BenchReport, Stage};
+use crate::{AocError, ParseError};
+
+/// Default location of the benchmark history.
+pub const DEFAULT_HISTORY: &str = "bench-history.jsonl";
+
+/// Commit and machine a benchmark was recorded on.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    pub commit: String,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    pub machine: String,
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Full commit hash `reference` points to, if git knows about it.
+pub fn resolve_commit(reference: &str) -> Option<String> {
+    git(&["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", reference)])
+}
+
+fn machine_name() -> String {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+impl Environment {
+    /// Detects the current commit and machine, falling back to `unknown` outside of a git checkout.
+    pub fn detect() -> Self {
+        Environment {
+            commit: resolve_commit("HEAD").unwrap_or_else(|| "unknown".to_string()),
+            dirty: git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty()),
+            machine: machine_name(),
+        }
+    }
+}
+
+/// One line of the benchmark history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub environment: Environment,
+    /// Optional name to refer to this run in comparisons, e.g. `baseline`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub report: BenchReport,
+}
+
+impl HistoryRecord {
+    pub fn new(environment: Environment, label: Option<String>, report: BenchReport) -> Self {
+        HistoryRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            environment,
+            label,
+            report,
+        }
+    }
+}
+
+/// Appends `records` to the history at `path`, creating the file if needed.
+pub fn append(path: &Path, records: &[HistoryRecord]) -> Result<(), AocError> {
+    let write_error = |source| AocError::Write { path: PathBuf::from(path), source };
+    let mut file = OpenOptions::new().create(true).append(true).open(path).map_err(write_error)?;
+    for record in records {
+        let line = serde_json::to_string(record).expect("History records are always serializable");
+        writeln!(file, "{}", line).map_err(write_error)?;
+    }
+    Ok(())
+}
+
+/// Reads all records of the history at `path`, a missing file is an empty history.
+pub fn load(path: &Path) -> Result<Vec<HistoryRecord>, AocError> {
+    let history_string = match fs::read_to_string(path) {
+        Ok(history_string) => history_string,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(AocError::Io { path: PathBuf::from(path), source: err }),
+    };
+    parse_history(&history_string)
+}
+
+pub fn parse_history(history_string: &str) -> Result<Vec<HistoryRecord>, AocError> {
+    history_string
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_index, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| ParseError::new(line_index + 1, err.column(), err.to_string()).into())
+        })
+        .collect()
+}
+
+/// Latest record of `day` on `machine` matching `reference`.
+///
+/// `reference` is matched against record labels first, then against commit hashes by prefix
+/// and finally resolved through git, so tags and branch names work as well.
+pub fn find_baseline<'a>(
+    records: &'a [HistoryRecord],
+    reference: &str,
+    machine: &str,
+    day: u8,
+) -> Option<&'a HistoryRecord> {
+    let candidates = records
+        .iter()
+        .filter(|record| record.environment.machine == machine && record.report.day == day)
+        .collect::<Vec<_>>();
+
+    let by_label = candidates.iter().rev().find(|record| record.label.as_deref() == Some(reference));
+    let by_commit = || {
+        candidates
+            .iter()
+            .rev()
+            .find(|record| !reference.is_empty() && record.environment.commit.starts_with(reference))
+    };
+    let by_git = || {
+        let commit = resolve_commit(reference)?;
+        candidates.iter().rev().find(|record| record.environment.commit == commit)
+    };
+    by_label.or_else(by_commit).or_else(by_git).copied()
+}
+
+/// Change of the median runtime of one stage between a baseline and the current run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+    /// Relative change in percent, positive values are slowdowns.
+    pub change_pct: f64,
+    pub regressed: bool,
+}
+
+/// Compares the medians of all stages present in both reports.
+///
+/// A stage regressed if its median is more than `threshold_pct` percent slower than the baseline.
+pub fn compare(baseline: &BenchReport, current: &BenchReport, threshold_pct: f64) -> Vec<Comparison> {
+    current
+        .stages
+        .iter()
+        .filter_map(|stage| {
+            let baseline_stage = baseline.stages.iter().find(|baseline_stage| baseline_stage.stage == stage.stage)?;
+            let baseline_ns = baseline_stage.stats.median_ns;
+            let current_ns = stage.stats.median_ns;
+            let change_pct = if baseline_ns == 0 {
+                0.0
+            } else {
+                (current_ns as f64 / baseline_ns as f64 - 1.0) * 100.0
+            };
+            Some(Comparison {
+                day: current.day,
+                stage: stage.stage,
+                baseline_ns,
+                current_ns,
+                change_pct,
+                regressed: change_pct > threshold_pct,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, find_baseline, parse_history, Environment, HistoryRecord};
+    use crate::bench::{BenchReport, Stage, StageReport, Stats};
+
+    fn report(day: u8, medians: [u64; 3]) -> BenchReport {
+        BenchReport {
+            day,
+            input: "problems/problem1.txt".to_string(),
+            input_bytes: 100,
+            stages: Stage::ALL
+                .iter()
+                .zip(medians)
+                .map(|(stage, median_ns)| StageReport {
+                    stage: *stage,
+                    stats: Stats { iterations: 1, min_ns: median_ns, median_ns, p95_ns: median_ns, bytes_per_sec: 1.0 },
+                })
+                .collect(),
+        }
+    }
+
+    fn record(commit: &str, label: Option<&str>, report: BenchReport) -> HistoryRecord {
+        let environment = Environment { commit: commit.to_string(), dirty: false, machine: "box".to_string() };
+        HistoryRecord::new(environment, label.map(str::to_string), report)
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let records = vec![record("abc123", Some("baseline"), report(1, [10, 20, 30]))];
+        let history = records.iter().map(|r| serde_json::to_string(r).unwrap() + "\n").collect::<String>();
+
+        assert_eq!(parse_history(&history).unwrap(), records);
+        assert_eq!(parse_history("{}\n").unwrap_err().exit_code(), 6);
+    }
+
+    #[test]
+    fn test_find_baseline() {
+        let records = vec![
+            record("abc123", Some("baseline"), report(1, [10, 20, 30])),
+            record("def456", None, report(1, [11, 21, 31])),
+            record("abc123", None, report(2, [12, 22, 32])),
+        ];
+
+        assert_eq!(find_baseline(&records, "baseline", "box", 1).unwrap().environment.commit, "abc123");
+        assert_eq!(find_baseline(&records, "def", "box", 1).unwrap().environment.commit, "def456");
+        assert_eq!(find_baseline(&records, "abc", "box", 2).unwrap().report.day, 2);
+        assert!(find_baseline(&records, "baseline", "other", 1).is_none());
+    }
+
+    #[test]
+    fn test_compare() {
+        let comparisons = compare(&report(1, [100, 100, 100]), &report(1, [105, 120, 80]), 10.0);
+
+        assert_eq!(comparisons.iter().map(|c| c.regressed).collect::<Vec<_>>(), [false, true, false]);
+        assert!((comparisons[1].change_pct - 20.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,127 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::{parse_input_file, AocError};
+
+/// Directory holding the puzzle inputs when no source is given.
+pub const DEFAULT_INPUT_DIR: &str = "problems";
+
+/// Where the puzzle input of a day is read from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputSource {
+    /// A single input file.
+    Path(PathBuf),
+    /// Standard input, selected with `-` on the command line.
+    Stdin,
+    /// Input held in memory, mainly for tests and generated inputs.
+    Inline(String),
+    /// A directory with one `problem<DAY>.txt` file per day.
+    Directory(PathBuf),
+}
+
+impl InputSource {
+    /// File name of the input of `day` inside an input directory.
+    pub fn day_file_name(day: u8) -> String {
+        format!("problem{}.txt", day)
+    }
+
+    /// Reads the complete input for `day`, only directories depend on the day.
+    pub fn read(&self, day: u8) -> Result<String, AocError> {
+        match self {
+            InputSource::Path(path) => parse_input_file(path),
+            InputSource::Stdin => {
+                let mut input_string = String::new();
+                match io::stdin().read_to_string(&mut input_string) {
+                    Ok(_) => Ok(input_string),
+                    Err(err) => Err(AocError::Io { path: PathBuf::from("-"), source: err }),
+                }
+            }
+            InputSource::Inline(input_string) => Ok(input_string.clone()),
+            InputSource::Directory(directory) => {
+                parse_input_file(directory.join(Self::day_file_name(day)))
+            }
+        }
+    }
+
+    /// Human readable location of the input of `day`.
+    pub fn location(&self, day: u8) -> String {
+        match self {
+            InputSource::Path(path) => path.display().to_string(),
+            InputSource::Stdin => "-".to_string(),
+            InputSource::Inline(_) => "<inline>".to_string(),
+            InputSource::Directory(directory) => {
+                directory.join(Self::day_file_name(day)).display().to_string()
+            }
+        }
+    }
+
+    pub fn is_directory(&self) -> bool {
+        matches!(self, InputSource::Directory(_))
+    }
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Directory(PathBuf::from(DEFAULT_INPUT_DIR))
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    /// `-` selects stdin, existing directories are used as input directories and
+    /// everything else is treated as a file path.
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let path = PathBuf::from(source);
+        if source == "-" {
+            Ok(InputSource::Stdin)
+        } else if path.is_dir() {
+            Ok(InputSource::Directory(path))
+        } else {
+            Ok(InputSource::Path(path))
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Path(path) | InputSource::Directory(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "-"),
+            InputSource::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use super::InputSource;
+    use crate::AocError;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(InputSource::from_str("-").unwrap(), InputSource::Stdin);
+        assert_eq!(InputSource::from_str("problems").unwrap(), InputSource::default());
+        assert_eq!(
+            InputSource::from_str("problems/problem5.txt").unwrap(),
+            InputSource::Path(PathBuf::from("problems/problem5.txt"))
+        );
+    }
+
+    #[test]
+    fn test_read() {
+        let inline = InputSource::Inline("1 2\n".to_string());
+        assert_eq!(inline.read(1).unwrap(), "1 2\n");
+
+        let directory = InputSource::default();
+        assert_eq!(directory.location(2), "problems/problem2.txt");
+        assert_eq!(directory.read(2).unwrap(), include_str!("../problems/problem2.txt"));
+        assert!(matches!(directory.read(25), Err(AocError::NotFound { .. })));
+    }
+}
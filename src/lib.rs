@@ -1,21 +1,107 @@
+use std::fmt::{Display, Formatter};
 use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
 
-pub fn parse_input_file(input_file_path: std::string::String) -> Result<String, String> {
-    let canonical_input_path = match fs::canonicalize(input_file_path) {
+pub mod alloc;
+pub mod animation;
+pub mod bench;
+pub mod error;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod logging;
+pub mod output;
+pub mod pattern;
+pub mod precedence;
+pub mod problems;
+pub mod registry;
+pub mod render;
+pub mod verify;
+pub mod word_search;
+
+pub use error::AocError;
+pub use input::InputSource;
+
+/// Error raised while turning the raw puzzle input into the typed input of a [`Solution`].
+///
+/// `line` and `column` are 1-based and point at the offending character.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, reason: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            reason: reason.into(),
+        }
+    }
+
+    /// Creates an error for the byte `offset` into `input`, resolving it to line and column.
+    pub fn at_offset(input: &str, offset: usize, reason: impl Into<String>) -> Self {
+        let prefix = &input[..offset];
+        let line = prefix.matches('\n').count() + 1;
+        let line_start = prefix.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        let column = prefix[line_start..].chars().count() + 1;
+        ParseError::new(line, column, reason)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A single day of the puzzle, split into parsing the input once and solving both parts on it.
+pub trait Solution {
+    /// Day of the advent calendar this solution belongs to.
+    const DAY: u8;
+
+    type Input;
+    type Output: Display;
+
+    /// Parses the puzzle input, failing with [`AocError::Parse`] on malformed input.
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, AocError>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, AocError>;
+
+    /// Reads the input of this day from `source` and parses it.
+    fn parse_source(source: &InputSource) -> Result<Self::Input, AocError> {
+        let input_string = source.read(Self::DAY)?;
+        Self::parse(&input_string)
+    }
+}
+
+pub fn parse_input_file(input_file_path: impl AsRef<Path>) -> Result<String, AocError> {
+    let input_path = input_file_path.as_ref().to_path_buf();
+    let canonical_input_path = match fs::canonicalize(&input_path) {
         Ok(path) => path,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(AocError::NotFound { path: input_path })
+        }
         Err(err) => {
-            return Err(format!("Cannot canonicalize input file: {}", err))
+            return Err(AocError::Canonicalize { path: input_path, source: err })
         }
     };
 
     if !canonical_input_path.exists() {
-        return Err(format!("Input file '{}' does not exist.", canonical_input_path.display()))
+        return Err(AocError::NotFound { path: canonical_input_path })
     }
 
     let input_string = match fs::read_to_string(&canonical_input_path) {
         Ok(string) => string,
         Err(err) => {
-            return Err(format!("Could not read input file: {:?} to string: {}", &canonical_input_path, err))
+            return Err(AocError::Io { path: canonical_input_path, source: err })
         }
     };
     Ok(input_string)
@@ -0,0 +1,111 @@
+use std::str::FromStr;
+
+use log::{LevelFilter, SetLoggerError};
+use simple_logger::SimpleLogger;
+
+/// Level used when neither a filter nor a verbosity flag sets one.
+pub const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// `RUST_LOG`-style log filter, e.g. `warn,day5=trace,advent_of_code_2024::registry=debug`.
+///
+/// Directives are separated by commas and are either a level, which sets the default level,
+/// a module, which enables all levels for it, or `module=level`. Modules match every target
+/// starting with them, `day<N>` is short for the module of the solution of day `N`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct LogFilter {
+    pub level: Option<LevelFilter>,
+    pub modules: Vec<(String, LevelFilter)>,
+}
+
+fn parse_level(level: &str, filter: &str) -> Result<LevelFilter, String> {
+    LevelFilter::from_str(level.trim()).map_err(|_| format!("Invalid log level '{}' in '{}'", level, filter))
+}
+
+/// Expands the `day<N>` shorthand to the module path of the solution.
+fn module_path(module: &str) -> String {
+    match module.strip_prefix("day").map(str::parse::<u8>) {
+        Some(Ok(day)) => format!("advent_of_code_2024::problems::problem{}", day),
+        _ => module.to_string(),
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = String;
+
+    fn from_str(filter: &str) -> Result<Self, Self::Err> {
+        let mut log_filter = LogFilter::default();
+        for directive in filter.split(',').map(str::trim).filter(|directive| !directive.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    log_filter.modules.push((module_path(module.trim()), parse_level(level, filter)?));
+                }
+                None => match LevelFilter::from_str(directive) {
+                    Ok(level) => log_filter.level = Some(level),
+                    Err(_) => log_filter.modules.push((module_path(directive), LevelFilter::Trace)),
+                },
+            }
+        }
+        Ok(log_filter)
+    }
+}
+
+/// Shifts `base` by the number of `-v` and `-q` flags, one level per flag.
+pub fn adjust_level(base: LevelFilter, verbose: u8, quiet: u8) -> LevelFilter {
+    const LEVELS: [LevelFilter; 6] = [
+        LevelFilter::Off,
+        LevelFilter::Error,
+        LevelFilter::Warn,
+        LevelFilter::Info,
+        LevelFilter::Debug,
+        LevelFilter::Trace,
+    ];
+    let index = LEVELS.iter().position(|level| *level == base).unwrap_or(3) as i32;
+    let adjusted = (index + verbose as i32 - quiet as i32).clamp(0, LEVELS.len() as i32 - 1);
+    LEVELS[adjusted as usize]
+}
+
+/// Installs the global logger, writing to stderr with the levels of `filter` and the verbosity flags.
+pub fn init(filter: &LogFilter, verbose: u8, quiet: u8) -> Result<(), SetLoggerError> {
+    let level = adjust_level(filter.level.unwrap_or(DEFAULT_LEVEL), verbose, quiet);
+    filter
+        .modules
+        .iter()
+        .fold(SimpleLogger::new().with_level(level), |logger, (module, level)| {
+            logger.with_module_level(module, *level)
+        })
+        .init()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use log::LevelFilter;
+
+    use super::{adjust_level, LogFilter};
+
+    #[test]
+    fn test_parse_filter() {
+        let filter = LogFilter::from_str("warn, day5=trace,advent_of_code_2024::registry").unwrap();
+
+        assert_eq!(filter.level, Some(LevelFilter::Warn));
+        assert_eq!(
+            filter.modules,
+            vec![
+                ("advent_of_code_2024::problems::problem5".to_string(), LevelFilter::Trace),
+                ("advent_of_code_2024::registry".to_string(), LevelFilter::Trace),
+            ]
+        );
+        assert_eq!(LogFilter::from_str("").unwrap(), LogFilter::default());
+        assert!(LogFilter::from_str("day5=loud").is_err());
+    }
+
+    #[test]
+    fn test_adjust_level() {
+        assert_eq!(adjust_level(LevelFilter::Info, 0, 0), LevelFilter::Info);
+        assert_eq!(adjust_level(LevelFilter::Info, 2, 0), LevelFilter::Trace);
+        assert_eq!(adjust_level(LevelFilter::Info, 5, 0), LevelFilter::Trace);
+        assert_eq!(adjust_level(LevelFilter::Warn, 0, 1), LevelFilter::Error);
+        assert_eq!(adjust_level(LevelFilter::Info, 0, 9), LevelFilter::Off);
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Output format of the answers printed by the runner.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// One `Day <DAY> part <PART>: <ANSWER>` line per answer
+    #[default]
+    Text,
+    /// A JSON array of records
+    Json,
+    /// Comma separated values with a header line
+    Csv,
+    /// Test Anything Protocol, failed parts are reported as `not ok`
+    Tap,
+}
+
+/// Answer of one part of one day.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct AnswerRecord {
+    pub day: u8,
+    pub part: u8,
+    /// `None` if the part failed, `error` holds the reason then.
+    pub answer: Option<String>,
+    /// Time spent solving the part, excluding parsing.
+    pub duration_ns: u64,
+    pub input_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Quotes `field` if it would otherwise break the CSV row.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_text(records: &[AnswerRecord]) -> String {
+    let mut output = String::new();
+    for record in records {
+        match (&record.answer, &record.error) {
+            (Some(answer), _) => writeln!(output, "Day {} part {}: {}", record.day, record.part, answer),
+            (None, error) => writeln!(
+                output,
+                "Day {} part {} failed: {}",
+                record.day,
+                record.part,
+                error.as_deref().unwrap_or("no answer")
+            ),
+        }
+        .unwrap();
+    }
+    output
+}
+
+fn render_csv(records: &[AnswerRecord]) -> String {
+    let mut output = String::from("day,part,answer,duration_ns,input_path,error\n");
+    for record in records {
+        writeln!(
+            output,
+            "{},{},{},{},{},{}",
+            record.day,
+            record.part,
+            csv_field(record.answer.as_deref().unwrap_or("")),
+            record.duration_ns,
+            csv_field(&record.input_path),
+            csv_field(record.error.as_deref().unwrap_or(""))
+        )
+        .unwrap();
+    }
+    output
+}
+
+fn render_tap(records: &[AnswerRecord]) -> String {
+    let mut output = format!("TAP version 13\n1..{}\n", records.len());
+    for (index, record) in records.iter().enumerate() {
+        let description = format!("day {} part {}", record.day, record.part);
+        match (&record.answer, &record.error) {
+            (Some(answer), _) => writeln!(output, "ok {} - {}: {}", index + 1, description, answer),
+            (None, error) => writeln!(
+                output,
+                "not ok {} - {}\n  ---\n  message: {:?}\n  input: {:?}\n  ...",
+                index + 1,
+                description,
+                error.as_deref().unwrap_or("no answer"),
+                record.input_path
+            ),
+        }
+        .unwrap();
+    }
+    output
+}
+
+/// Renders all `records` in `format`, the result ends with a newline unless it is empty.
+pub fn render(format: Format, records: &[AnswerRecord]) -> String {
+    match format {
+        Format::Text => render_text(records),
+        Format::Json => serde_json::to_string_pretty(records).expect("Answer records are always serializable") + "\n",
+        Format::Csv => render_csv(records),
+        Format::Tap => render_tap(records),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, AnswerRecord, Format};
+
+    fn records() -> Vec<AnswerRecord> {
+        vec![
+            AnswerRecord {
+                day: 1,
+                part: 1,
+                answer: Some("11".to_string()),
+                duration_ns: 1500,
+                input_path: "problems/problem1_test.txt".to_string(),
+                error: None,
+            },
+            AnswerRecord {
+                day: 6,
+                part: 2,
+                answer: None,
+                duration_ns: 0,
+                input_path: "a,b.txt".to_string(),
+                error: Some("Day 6 part 2 has no solution: loop".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_text_and_csv() {
+        assert_eq!(
+            render(Format::Text, &records()),
+            "Day 1 part 1: 11\nDay 6 part 2 failed: Day 6 part 2 has no solution: loop\n"
+        );
+        assert_eq!(
+            render(Format::Csv, &records()),
+            "day,part,answer,duration_ns,input_path,error\n\
+             1,1,11,1500,problems/problem1_test.txt,\n\
+             6,2,,0,\"a,b.txt\",Day 6 part 2 has no solution: loop\n"
+        );
+    }
+
+    #[test]
+    fn test_render_json_and_tap() {
+        let json: serde_json::Value = serde_json::from_str(&render(Format::Json, &records())).unwrap();
+        assert_eq!(json[0]["answer"], "11");
+        assert_eq!(json[0]["duration_ns"], 1500);
+        assert!(json[0].get("error").is_none());
+        assert!(json[1]["answer"].is_null());
+
+        let tap = render(Format::Tap, &records());
+        assert!(tap.starts_with("TAP version 13\n1..2\nok 1 - day 1 part 1: 11\nnot ok 2 - day 6 part 2\n"));
+    }
+}
@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use ndarray::Array2;
+
+use crate::grid::{Dir, Grid, Pos};
+use crate::ParseError;
+
+/// Character matching any cell in a pattern.
+pub const WILDCARD: char = '.';
+
+/// Rotation or reflection applied to a pattern before matching.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Transform {
+    Identity,
+    /// Clockwise by 90 degrees.
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirrored left to right.
+    FlipHorizontal,
+    /// Mirrored top to bottom.
+    FlipVertical,
+    /// Mirrored along the main diagonal.
+    Transpose,
+    /// Mirrored along the anti-diagonal.
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ROTATIONS: [Transform; 4] =
+        [Transform::Identity, Transform::Rotate90, Transform::Rotate180, Transform::Rotate270];
+    /// All rotations and reflections of a rectangle.
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    /// Whether the transform swaps rows and columns.
+    fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90 | Transform::Rotate270 | Transform::Transpose | Transform::AntiTranspose
+        )
+    }
+
+    /// Position of `pos` of a `rows` x `cols` rectangle after the transform.
+    fn apply(self, pos: Pos, rows: usize, cols: usize) -> Pos {
+        let (r, c) = (pos.row, pos.col);
+        match self {
+            Transform::Identity => Pos::new(r, c),
+            Transform::Rotate90 => Pos::new(c, rows - 1 - r),
+            Transform::Rotate180 => Pos::new(rows - 1 - r, cols - 1 - c),
+            Transform::Rotate270 => Pos::new(cols - 1 - c, r),
+            Transform::FlipHorizontal => Pos::new(r, cols - 1 - c),
+            Transform::FlipVertical => Pos::new(rows - 1 - r, c),
+            Transform::Transpose => Pos::new(c, r),
+            Transform::AntiTranspose => Pos::new(cols - 1 - c, rows - 1 - r),
+        }
+    }
+}
+
+/// Small grid of characters where `None` cells match anything.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Pattern {
+    cells: Grid<Option<char>>,
+}
+
+impl Pattern {
+    pub fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.cols()
+    }
+
+    pub fn transformed(&self, transform: Transform) -> Pattern {
+        let (rows, cols) = (self.rows(), self.cols());
+        let shape = if transform.swaps_axes() { (cols, rows) } else { (rows, cols) };
+        let mut cells = Grid::from_array(Array2::from_elem(shape, None));
+        for (pos, cell) in self.cells.iter() {
+            cells[transform.apply(pos, rows, cols)] = *cell;
+        }
+        Pattern { cells }
+    }
+
+    /// Offsets of the cells that must match relative to the top left corner.
+    fn required_cells(&self) -> Vec<(Dir, char)> {
+        self.cells
+            .iter()
+            .filter_map(|(pos, cell)| cell.map(|c| (Pos::default().dir_to(pos), c)))
+            .collect()
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = ParseError;
+
+    /// Rows are separated by `/` or newlines, `.` is a wildcard, e.g. `M.S/.A./M.S`.
+    fn from_str(pattern_string: &str) -> Result<Self, Self::Err> {
+        let cells = Grid::parse(&pattern_string.replace('/', "\n"), |_, c| {
+            Ok(if c == WILDCARD { None } else { Some(c) })
+        })?;
+        Ok(Pattern { cells })
+    }
+}
+
+/// Occurrence of a pattern in a grid.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PatternMatch {
+    /// Top left corner of the transformed pattern.
+    pub pos: Pos,
+    pub transform: Transform,
+    /// Positions of the non-wildcard cells.
+    pub positions: Vec<Pos>,
+}
+
+/// Pattern after applying one transform.
+#[derive(Debug, Clone)]
+struct Variant {
+    transform: Transform,
+    pattern: Pattern,
+    required: Vec<(Dir, char)>,
+}
+
+/// Matches a pattern under a set of transforms at every position of a grid.
+#[derive(Debug, Clone)]
+pub struct TemplateMatcher {
+    variants: Vec<Variant>,
+}
+
+impl TemplateMatcher {
+    /// Creates a matcher for `pattern` under each of `transforms`.
+    ///
+    /// Transforms leaving a symmetric pattern unchanged are only matched once, so every
+    /// occurrence is reported with the first transform in `transforms` producing it.
+    pub fn new(pattern: &Pattern, transforms: &[Transform]) -> Self {
+        let mut variants: Vec<Variant> = Vec::with_capacity(transforms.len());
+        for transform in transforms {
+            let transformed = pattern.transformed(*transform);
+            if variants.iter().all(|variant| variant.pattern != transformed) {
+                variants.push(Variant {
+                    transform: *transform,
+                    required: transformed.required_cells(),
+                    pattern: transformed,
+                });
+            }
+        }
+        TemplateMatcher { variants }
+    }
+
+    /// Every match in `grid`, ordered by position.
+    pub fn find(&self, grid: &Grid<char>) -> Vec<PatternMatch> {
+        let mut matches = Vec::new();
+        for (pos, _) in grid.iter() {
+            for variant in &self.variants {
+                let pattern = &variant.pattern;
+                if pos.row + pattern.rows() > grid.rows() || pos.col + pattern.cols() > grid.cols() {
+                    continue;
+                }
+                let positions = variant
+                    .required
+                    .iter()
+                    .map(|(offset, c)| pos.offset(*offset).filter(|cell| grid[*cell] == *c))
+                    .collect::<Option<Vec<_>>>();
+                if let Some(positions) = positions {
+                    matches.push(PatternMatch { pos, transform: variant.transform, positions });
+                }
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Pattern, TemplateMatcher, Transform};
+    use crate::grid::{Grid, Pos};
+    use crate::ParseError;
+
+    #[test]
+    fn test_transform() {
+        let pattern = Pattern::from_str("ab./..c").unwrap();
+
+        assert_eq!(pattern.transformed(Transform::Rotate90), Pattern::from_str(".a/.b/c.").unwrap());
+        assert_eq!(pattern.transformed(Transform::Rotate180), Pattern::from_str("c../.ba").unwrap());
+        assert_eq!(pattern.transformed(Transform::FlipHorizontal), Pattern::from_str(".ba/c..").unwrap());
+        assert_eq!(pattern.transformed(Transform::AntiTranspose), Pattern::from_str("c./.b/.a").unwrap());
+        assert_eq!(
+            Pattern::from_str("ab/c").unwrap_err(),
+            ParseError::new(2, 2, "Row has 1 columns, expected 2")
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::parse(include_str!("../problems/problem4_test.txt"), |_, c| Ok(c)).unwrap();
+        let x_mas = Pattern::from_str("M.S/.A./M.S").unwrap();
+
+        let rotations = TemplateMatcher::new(&x_mas, &Transform::ROTATIONS).find(&grid);
+        assert_eq!(rotations.len(), 9);
+        assert_eq!(TemplateMatcher::new(&x_mas, &Transform::ALL).find(&grid), rotations);
+        assert_eq!(TemplateMatcher::new(&x_mas, &[Transform::Identity]).find(&grid).len(), 2);
+
+        let first = &rotations[0];
+        assert_eq!(first.pos, Pos::new(0, 1));
+        assert_eq!(first.transform, Transform::Identity);
+        assert_eq!(first.positions.len(), 5);
+    }
+}
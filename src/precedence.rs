@@ -0,0 +1,247 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::iter;
+
+use itertools::Itertools;
+
+/// Rules that some elements have to come before others, e.g. the page ordering rules of day 5.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PrecedenceRules<T: Hash + Eq> {
+    /// Elements that have to come after each element.
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T: Hash + Eq> Default for PrecedenceRules<T> {
+    fn default() -> Self {
+        PrecedenceRules { successors: HashMap::new() }
+    }
+}
+
+impl<T: Hash + Eq + Clone> PrecedenceRules<T> {
+    /// Creates the rules from `(before, after)` pairs, repeated pairs are only kept once.
+    pub fn from_pairs(pairs: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut rules = PrecedenceRules::default();
+        for (before, after) in pairs {
+            rules.insert(before, after);
+        }
+        rules
+    }
+
+    /// Adds the rule that `before` comes before `after`, returns `false` if it already existed.
+    pub fn insert(&mut self, before: T, after: T) -> bool {
+        self.successors.entry(before).or_default().insert(after)
+    }
+
+    /// Whether a rule puts `before` before `after`.
+    pub fn requires(&self, before: &T, after: &T) -> bool {
+        self.successors.get(before).is_some_and(|successors| successors.contains(after))
+    }
+
+    /// Elements a rule puts after `element`, in no particular order.
+    pub fn successors<'a>(&'a self, element: &T) -> impl Iterator<Item = &'a T> + 'a {
+        self.successors.get(element).into_iter().flatten()
+    }
+
+    /// Every rule as `(before, after)` pair, in no particular order.
+    pub fn pairs(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.successors
+            .iter()
+            .flat_map(|(before, successors)| successors.iter().map(move |after| (before, after)))
+    }
+
+    /// Whether no element of `items` comes after an element a rule puts after it.
+    pub fn is_ordered(&self, items: &[T]) -> bool {
+        items.iter().enumerate().all(|(index, item)| {
+            self.successors
+                .get(item)
+                .is_none_or(|successors| items[..index].iter().all(|earlier| !successors.contains(earlier)))
+        })
+    }
+
+    /// Indices `(earlier, later)` of every pair in `items` where a rule puts the later element first.
+    pub fn violations(&self, items: &[T]) -> Vec<(usize, usize)> {
+        let mut violations = Vec::new();
+        for (earlier, first) in items.iter().enumerate() {
+            for (later, second) in items.iter().enumerate().skip(earlier + 1) {
+                if self.requires(second, first) {
+                    violations.push((earlier, later));
+                }
+            }
+        }
+        violations
+    }
+
+    /// Orders `items` with Kahn's algorithm on the rules between them.
+    ///
+    /// Only rules whose elements both appear in `items` are considered. If several elements are
+    /// ready at once, the one coming first in `items` is taken first, so elements without rules
+    /// between them keep their order. Repeated elements end up next to each other.
+    pub fn topological_sort(&self, items: &[T]) -> Result<Order<T>, Cycle<T>> {
+        // Distinct elements in the order of their first occurrence, with how often they occur.
+        let mut indices = HashMap::<&T, usize>::new();
+        let mut distinct = Vec::<(&T, usize)>::new();
+        for item in items {
+            match indices.get(item) {
+                Some(index) => distinct[*index].1 += 1,
+                None => {
+                    indices.insert(item, distinct.len());
+                    distinct.push((item, 1));
+                }
+            }
+        }
+        let successors = |index: usize| {
+            self.successors(distinct[index].0).filter_map(|successor| indices.get(successor).copied())
+        };
+
+        let mut in_degrees = vec![0usize; distinct.len()];
+        for successor in (0..distinct.len()).flat_map(successors) {
+            in_degrees[successor] += 1;
+        }
+
+        let mut ready = (0..distinct.len()).filter(|index| in_degrees[*index] == 0).collect::<BTreeSet<_>>();
+        let mut order = Vec::with_capacity(items.len());
+        let mut ambiguity = None;
+        while let Some(index) = ready.pop_first() {
+            if !ready.is_empty() && ambiguity.is_none() {
+                let candidates = iter::once(index).chain(ready.iter().copied()).map(|index| distinct[index].0.clone());
+                ambiguity = Some(Ambiguity { position: order.len(), candidates: candidates.collect() });
+            }
+            let (item, count) = distinct[index];
+            order.extend(iter::repeat_n(item.clone(), count));
+            for successor in successors(index) {
+                in_degrees[successor] -= 1;
+                if in_degrees[successor] == 0 {
+                    ready.insert(successor);
+                }
+            }
+        }
+
+        if order.len() < items.len() {
+            let remaining = (0..distinct.len()).filter(|index| in_degrees[*index] > 0).collect::<HashSet<_>>();
+            let cycle = find_cycle(&remaining, successors);
+            return Err(Cycle { items: cycle.into_iter().map(|index| distinct[index].0.clone()).collect() });
+        }
+        Ok(Order { items: order, ambiguity })
+    }
+
+    /// Sorts `items` so that they satisfy all rules between them, see [`PrecedenceRules::topological_sort`].
+    pub fn sort(&self, items: &mut [T]) -> Result<(), Cycle<T>> {
+        let order = self.topological_sort(items)?;
+        items.clone_from_slice(&order.items);
+        Ok(())
+    }
+}
+
+/// Elements that could all come at `position` of a sequence, the rules allow more than one order.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Ambiguity<T> {
+    pub position: usize,
+    /// The elements in the order they first occur in the items being sorted.
+    pub candidates: Vec<T>,
+}
+
+impl<T: Debug> Display for Ambiguity<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} could all come at position {}", self.candidates, self.position + 1)
+    }
+}
+
+/// Elements in an order satisfying the rules between them.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Order<T> {
+    pub items: Vec<T>,
+    /// The first position where the rules allow another element, `None` if the order is unique.
+    pub ambiguity: Option<Ambiguity<T>>,
+}
+
+/// Rules between elements that form a cycle, no order satisfies them.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cycle<T> {
+    /// Elements along the cycle starting with the one that occurs first in the items being sorted, each
+    /// has to come before the next and the last before the first.
+    pub items: Vec<T>,
+}
+
+impl<T: Display> Display for Cycle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let first = self.items.first().map(|item| format!(" -> {}", item)).unwrap_or_default();
+        write!(f, "Rules between {}{} form a cycle", self.items.iter().join(" -> "), first)
+    }
+}
+
+/// Indices of a cycle among `remaining`, elements that all have a predecessor among them.
+///
+/// The cycle starts with its smallest index and follows `successors`.
+fn find_cycle<I: Iterator<Item = usize>>(remaining: &HashSet<usize>, successors: impl Fn(usize) -> I) -> Vec<usize> {
+    let mut predecessors = HashMap::<usize, usize>::new();
+    for index in remaining {
+        for successor in successors(*index).filter(|successor| remaining.contains(successor)) {
+            let predecessor = predecessors.entry(successor).or_insert(*index);
+            *predecessor = (*predecessor).min(*index);
+        }
+    }
+
+    // Walking backwards along the rules has to revisit an element, the ones since then form a cycle.
+    let mut path = vec![*remaining.iter().min().expect("A cycle has elements")];
+    let mut seen = HashSet::from([path[0]]);
+    loop {
+        let predecessor = predecessors[path.last().expect("The path is never empty")];
+        if !seen.insert(predecessor) {
+            let start = path.iter().position(|index| *index == predecessor).expect("Seen elements are on the path");
+            let mut cycle = path.split_off(start);
+            cycle.reverse();
+            let smallest = cycle.iter().position_min().expect("A cycle has elements");
+            cycle.rotate_left(smallest);
+            return cycle;
+        }
+        path.push(predecessor);
+    }
+}
+
+/// Element in the middle of `items`, the later one of the two middle elements for even lengths.
+pub fn middle<T>(items: &[T]) -> Option<&T> {
+    items.get(items.len() / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{middle, Ambiguity, Cycle, Order, PrecedenceRules};
+
+    #[test]
+    fn test_rules() {
+        let rules = PrecedenceRules::from_pairs([("a", "b"), ("b", "c"), ("a", "c"), ("a", "b")]);
+
+        assert_eq!(rules.pairs().count(), 3);
+        assert!(rules.is_ordered(&["a", "b", "c"]));
+        assert!(rules.is_ordered(&["a", "x", "c"]));
+        assert!(!rules.is_ordered(&["c", "a"]));
+        assert_eq!(rules.violations(&["c", "b", "a"]), vec![(0, 1), (0, 2), (1, 2)]);
+
+        let mut items = ["c", "a", "b"];
+        rules.sort(&mut items).unwrap();
+        assert_eq!(items, ["a", "b", "c"]);
+        assert_eq!(middle(&items), Some(&"b"));
+        assert_eq!(middle::<u64>(&[]), None);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let rules = PrecedenceRules::from_pairs([("a", "c")]);
+        let mut items = ["c", "b", "a"];
+        rules.sort(&mut items).unwrap();
+        assert_eq!(items, ["b", "a", "c"]);
+        assert_eq!(
+            rules.topological_sort(&["c", "b", "a"]).unwrap(),
+            Order { items: vec!["b", "a", "c"], ambiguity: Some(Ambiguity { position: 0, candidates: vec!["b", "a"] }) }
+        );
+        assert_eq!(rules.topological_sort(&["c", "a", "c"]).unwrap().items, vec!["a", "c", "c"]);
+
+        let cyclic = PrecedenceRules::from_pairs([(1, 2), (2, 3), (3, 1), (3, 4)]);
+        let cycle = cyclic.topological_sort(&[4, 1, 2, 3]).unwrap_err();
+        assert_eq!(cycle, Cycle { items: vec![1, 2, 3] });
+        assert_eq!(cycle.to_string(), "Rules between 1 -> 2 -> 3 -> 1 form a cycle");
+        assert_eq!(cyclic.topological_sort(&[4, 2, 3, 1]).unwrap_err().items, vec![2, 3, 1]);
+        assert_eq!(PrecedenceRules::from_pairs([(5, 5)]).topological_sort(&[5]).unwrap_err().items, vec![5]);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod problem1;
+pub mod problem2;
+pub mod problem3;
+pub mod problem4;
+pub mod problem5;
+pub mod problem6;
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::{AocError, ParseError, Solution};
+
+pub struct Problem1;
+
+impl Solution for Problem1 {
+    const DAY: u8 = 1;
+
+    type Input = (Vec<u64>, Vec<u64>);
+    type Output = u64;
+
+    fn parse(input_string: &str) -> Result<Self::Input, AocError> {
+        let re = Regex::new(r"(?<first>[0-9]+)\s+(?<second>[0-9]+)")?;
+
+        let mut list_left = Vec::<u64>::new();
+        let mut list_right = Vec::<u64>::new();
+
+        for caps in re.captures_iter(input_string) {
+            let first = caps.name("first").unwrap();
+            let second = caps.name("second").unwrap();
+            let first_number = match u64::from_str(first.as_str()) {
+                Ok(number) => number,
+                Err(err) => {
+                    return Err(ParseError::at_offset(
+                        input_string,
+                        first.start(),
+                        format!("Could not parse first number: {}", err),
+                    )
+                    .into());
+                }
+            };
+            let second_number = match u64::from_str(second.as_str()) {
+                Ok(number) => number,
+                Err(err) => {
+                    return Err(ParseError::at_offset(
+                        input_string,
+                        second.start(),
+                        format!("Could not parse second number: {}", err),
+                    )
+                    .into());
+                }
+            };
+            list_left.push(first_number);
+            list_right.push(second_number);
+        }
+
+        Ok((list_left, list_right))
+    }
+
+    fn part1((list_left, list_right): &Self::Input) -> Result<Self::Output, AocError> {
+        let mut list_left = list_left.clone();
+        let mut list_right = list_right.clone();
+        list_left.sort();
+        list_right.sort();
+
+        Ok(list_left.iter().zip(list_right.iter()).map(|(a, b)| a.abs_diff(*b)).sum())
+    }
+
+    fn part2((list_left, list_right): &Self::Input) -> Result<Self::Output, AocError> {
+        let mut right = HashMap::<u64, u64>::new();
+        for second_number in list_right {
+            *right.entry(*second_number).or_insert(0) += 1;
+        }
+
+        let mut result: u64 = 0;
+
+        for left in list_left {
+            result += left * right.get(left).unwrap_or(&0u64);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Problem1;
+    use crate::Solution;
+
+    #[test]
+    fn test_example() {
+        let input_string = include_str!("../../problems/problem1_test.txt");
+        let input = Problem1::parse(input_string).unwrap();
+
+        assert_eq!(Problem1::part1(&input).unwrap(), 11);
+        assert_eq!(Problem1::part2(&input).unwrap(), 31);
+    }
+}
@@ -0,0 +1,150 @@
+use log::debug;
+use std::str::FromStr;
+
+use itertools::{Itertools, MinMaxResult};
+
+use crate::{AocError, ParseError, Solution};
+
+pub struct Problem2;
+
+impl Solution for Problem2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Vec<i64>>;
+    type Output = u64;
+
+    fn parse(input_str: &str) -> Result<Self::Input, AocError> {
+        Ok(parse_input(input_str)?)
+    }
+
+    fn part1(configs: &Self::Input) -> Result<Self::Output, AocError> {
+        Ok(calculate_safe_configs(configs))
+    }
+
+    fn part2(configs: &Self::Input) -> Result<Self::Output, AocError> {
+        Ok(calculate_safe_configs_with_dampener(configs))
+    }
+}
+
+fn calculate_safe_configs(configs: &[Vec<i64>]) -> u64 {
+    let mut safe_configs: u64 = 0;
+    for config in configs {
+
+        let diffs = config.iter().tuple_windows().map(|(f, s)| f - s).collect::<Vec<_>>();
+        let invalid_nos = diffs.iter().map(|i| i.abs()).filter(|i| !(1..=3).contains(i)).count();
+
+        if invalid_nos > 0 {
+            debug!("❌ {:?} - Diffs {:?}; {} are not within bounds!", config, diffs, invalid_nos);
+            continue;
+        }
+
+        match diffs.iter().minmax() {
+            MinMaxResult::MinMax(min, max) => {
+                if (min * max) > 0 {
+                    debug!("✓ {:?} - Diffs {:?};", config, diffs);
+                    safe_configs += 1;
+                } else {
+                    debug!("❌ {:?} - Diffs {:?}; Not continuous: Min: {}; Max: {}", config, diffs, min, max);
+                }
+                continue;
+            },
+            MinMaxResult::OneElement(_) | MinMaxResult::NoElements => {
+                debug!("❌ {:?} - Diffs {:?}; List does not contain correct number of elements!", config, diffs);
+                continue;
+            }
+        }
+    }
+    safe_configs
+}
+
+fn diff_range_valid(config: &[i64]) -> bool {
+    let invalid_diffs = config
+        .iter()
+        .tuple_windows()
+        .map(|(i, j)| i.abs_diff(*j))
+        .filter(|i| !(1..=3).contains(i))
+        .count();
+
+    invalid_diffs == 0
+}
+
+fn generate_configs(base_config: &[i64]) -> Vec<Vec<i64>> {
+    let mut output = vec![base_config.to_vec()];
+
+    for combination in base_config.iter().combinations(base_config.len().saturating_sub(1)) {
+        output.push(combination.iter().map(|i| **i).collect());
+    }
+
+    output
+}
+
+fn check_if_ascending_descending(config: &[i64]) -> bool {
+    let diffs = config
+        .iter()
+        .tuple_windows()
+        .map(|(f, s)| f - s)
+        .collect::<Vec<_>>();
+    match diffs.iter().minmax() {
+        MinMaxResult::MinMax(min, max) => (min * max) > 0,
+        MinMaxResult::OneElement(_) | MinMaxResult::NoElements => false,
+    }
+}
+
+fn calculate_safe_configs_with_dampener(configs: &[Vec<i64>]) -> u64 {
+    let mut safe_configs: u64 = 0;
+    for config in configs {
+        let variation_configs = generate_configs(config.as_ref());
+        let valid_configs = variation_configs
+            .iter()
+            .filter(|a| diff_range_valid(a.as_ref()))
+            .filter(|a| check_if_ascending_descending(a.as_ref()))
+            .collect::<Vec<_>>();
+
+        if !valid_configs.is_empty() {
+            debug!("✓ {:?} - Found {} configs!", config, valid_configs.len());
+            safe_configs += 1;
+        } else {
+            debug!("❌ {:?}", config);
+        }
+    }
+    safe_configs
+}
+
+fn parse_input(input_str: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input_str
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_index, line)| {
+            line.split_whitespace()
+                .map(|e| {
+                    i64::from_str(e).map_err(|err| {
+                        let column = e.as_ptr() as usize - line.as_ptr() as usize + 1;
+                        ParseError::new(line_index + 1, column, format!("Invalid level '{}': {}", e, err))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_input, Problem2};
+    use crate::{ParseError, Solution};
+
+    #[test]
+    fn test_example() {
+        let input_string = include_str!("../../problems/problem2_test.txt");
+        let configs = Problem2::parse(input_string).unwrap();
+
+        assert_eq!(Problem2::part1(&configs).unwrap(), 2);
+        assert_eq!(Problem2::part2(&configs).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_invalid_level() {
+        let err = parse_input("1 2 3\n4 x 6\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 3, "Invalid level 'x': invalid digit found in string"));
+    }
+}
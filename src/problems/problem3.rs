@@ -0,0 +1,107 @@
+use std::ops::Mul;
+
+use regex::Regex;
+
+use crate::{AocError, ParseError, Solution};
+
+pub struct Problem3;
+
+/// Instruction found in the corrupted memory.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+    Mul(i64, i64),
+    Do,
+    Dont,
+}
+
+impl Solution for Problem3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Instruction>;
+    type Output = i64;
+
+    fn parse(input_string: &str) -> Result<Self::Input, AocError> {
+        parse_instructions(input_string)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<Self::Output, AocError> {
+        let mult_pairs = instructions.iter().filter_map(|instruction| match instruction {
+            Instruction::Mul(first, second) => Some((*first, *second)),
+            Instruction::Do | Instruction::Dont => None,
+        });
+        Ok(multiply_pairs_and_add(mult_pairs))
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<Self::Output, AocError> {
+        Ok(multiply_pairs_and_add(enabled_mult_pairs(instructions)))
+    }
+}
+
+fn parse_instructions(input_string: &str) -> Result<Vec<Instruction>, AocError> {
+    let regex = Regex::new(r"mul\((?<first>\d+),(?<second>\d+)\)|(?<do>do\(\))|(?<dont>don't\(\))")?;
+    let parse_number = |number: regex::Match| {
+        number.as_str().parse::<i64>().map_err(|err| {
+            ParseError::at_offset(input_string, number.start(), format!("Invalid number '{}': {}", number.as_str(), err))
+        })
+    };
+
+    let mut output = Vec::new();
+    for capture in regex.captures_iter(input_string) {
+        let instruction = match (capture.name("first"), capture.name("second")) {
+            (Some(first), Some(second)) => Instruction::Mul(parse_number(first)?, parse_number(second)?),
+            _ if capture.name("do").is_some() => Instruction::Do,
+            _ => Instruction::Dont,
+        };
+        output.push(instruction);
+    }
+    Ok(output)
+}
+
+/// Multiplications that are not disabled by a preceding `don't()` without a `do()` after it.
+fn enabled_mult_pairs(instructions: &[Instruction]) -> impl Iterator<Item = (i64, i64)> + '_ {
+    instructions
+        .iter()
+        .scan(true, |enabled, instruction| {
+            Some(match instruction {
+                Instruction::Mul(first, second) if *enabled => Some((*first, *second)),
+                Instruction::Mul(..) => None,
+                Instruction::Do | Instruction::Dont => {
+                    *enabled = *instruction == Instruction::Do;
+                    None
+                }
+            })
+        })
+        .flatten()
+}
+
+fn multiply_pairs_and_add(pairs: impl Iterator<Item = (i64, i64)>) -> i64 {
+    pairs.map(|(f, s)| f.mul(s)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Problem3;
+    use crate::{AocError, Solution};
+
+    #[test]
+    fn test_example() {
+        let input_string = include_str!("../../problems/problem3_test.txt");
+        let input = Problem3::parse(input_string).unwrap();
+
+        assert_eq!(Problem3::part1(&input).unwrap(), 161);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        let input_string = include_str!("../../problems/problem3_test2.txt");
+        let input = Problem3::parse(input_string).unwrap();
+
+        assert_eq!(Problem3::part2(&input).unwrap(), 48);
+    }
+
+    #[test]
+    fn test_number_overflow() {
+        let err = Problem3::parse("do()\nxmul(2,99999999999999999999)").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 2, column: 8, .. }), "{:?}", err);
+    }
+}
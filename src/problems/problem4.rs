@@ -0,0 +1,136 @@
+use log::debug;
+use std::str::FromStr;
+
+use crate::grid::{Grid, Pos};
+use crate::pattern::{Pattern, PatternMatch, TemplateMatcher, Transform};
+use crate::render::{Color, Image, Overlay, Palette};
+use crate::word_search::{SearchConfig, WordMatch, WordSearch};
+use crate::{AocError, Solution};
+
+pub struct Problem4;
+
+impl Solution for Problem4 {
+    const DAY: u8 = 4;
+
+    type Input = Grid<char>;
+    type Output = usize;
+
+    fn parse(input_string: &str) -> Result<Self::Input, AocError> {
+        Ok(Grid::parse(input_string, |_, c| Ok(c))?)
+    }
+
+    fn part1(search_matrix: &Self::Input) -> Result<Self::Output, AocError> {
+        let found_words = find_words_in_matrix(search_matrix);
+
+        debug!(
+            "\n{}",
+            visualize_matches(
+                found_words.as_ref(),
+                search_matrix.rows(),
+                search_matrix.cols(),
+            )
+        );
+        Ok(found_words.len())
+    }
+
+    fn part2(search_matrix: &Self::Input) -> Result<Self::Output, AocError> {
+        let found_crosses = find_crosses_in_matrix(search_matrix);
+
+        debug!("\n{}", visualize_crosses(found_crosses.as_ref(), search_matrix));
+        Ok(found_crosses.len())
+    }
+}
+
+fn find_words_in_matrix(search_matrix: &Grid<char>) -> Vec<WordMatch> {
+    WordSearch::new(["XMAS"], SearchConfig::default()).find(search_matrix)
+}
+
+fn visualize_matches(found_words: &[WordMatch], rows: usize, cols: usize) -> String {
+    let mut grid = Grid::from_elem(rows, cols, '.');
+    for found_word in found_words {
+        for (pos, c) in found_word.positions.iter().zip(found_word.word.chars()) {
+            grid[*pos] = c;
+        }
+    }
+    grid.to_string()
+}
+
+fn find_crosses_in_matrix(search_matrix: &Grid<char>) -> Vec<PatternMatch> {
+    let x_mas = Pattern::from_str("M.S/.A./M.S").expect("X-MAS pattern is valid");
+    TemplateMatcher::new(&x_mas, &Transform::ROTATIONS).find(search_matrix)
+}
+
+fn visualize_crosses(found_crosses: &[PatternMatch], search_grid: &Grid<char>) -> String {
+    let mut grid = Grid::from_elem(search_grid.rows(), search_grid.cols(), '.');
+    for found_cross in found_crosses {
+        for pos in &found_cross.positions {
+            grid[*pos] = search_grid[*pos];
+        }
+    }
+    grid.to_string()
+}
+
+const BACKGROUND: Color = Color::rgb(240, 240, 240);
+const MATCHED: Color = Color::rgb(255, 213, 79);
+const OUTLINE: Color = Color::rgb(211, 47, 47);
+
+/// Picture of the grid with the cells of every match highlighted and outlined.
+fn matches_image<'a, 'b>(grid: &'a Grid<char>, matches: impl IntoIterator<Item = &'b [Pos]>) -> Image<'a, char> {
+    let matches = matches.into_iter().collect::<Vec<_>>();
+    let image = Image::new(grid, Palette::new(BACKGROUND)).overlay(Overlay::Highlight {
+        cells: matches.iter().flat_map(|positions| positions.iter().copied()).collect(),
+        color: MATCHED,
+    });
+    matches.into_iter().fold(image, |image, positions| {
+        image.overlay(Overlay::Outline { cells: positions.to_vec(), color: OUTLINE })
+    })
+}
+
+/// Picture of every `XMAS` found for part 1.
+pub fn words_image(search_matrix: &Grid<char>) -> Image<'_, char> {
+    let found_words = find_words_in_matrix(search_matrix);
+    matches_image(search_matrix, found_words.iter().map(|found_word| found_word.positions.as_slice()))
+}
+
+/// Picture of every X-MAS found for part 2.
+pub fn crosses_image(search_matrix: &Grid<char>) -> Image<'_, char> {
+    let found_crosses = find_crosses_in_matrix(search_matrix);
+    matches_image(search_matrix, found_crosses.iter().map(|found_cross| found_cross.positions.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_crosses_in_matrix, find_words_in_matrix, visualize_crosses, visualize_matches, Problem4};
+    use crate::Solution;
+
+    #[test]
+    fn test_example() {
+        let input_string = include_str!("../../problems/problem4_test.txt");
+        let search_matrix = Problem4::parse(input_string).unwrap();
+
+        let found_words = find_words_in_matrix(&search_matrix);
+
+        visualize_matches(
+            found_words.as_ref(),
+            search_matrix.rows(),
+            search_matrix.cols(),
+        );
+
+        assert_eq!(found_words.len(), 18);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        let input_string = include_str!("../../problems/problem4_test.txt");
+        let search_matrix = Problem4::parse(input_string).unwrap();
+
+        let found_crosses = find_crosses_in_matrix(&search_matrix);
+
+        visualize_crosses(
+            found_crosses.as_ref(),
+            &search_matrix,
+        );
+
+        assert_eq!(found_crosses.len(), 9);
+    }
+}
@@ -0,0 +1,619 @@
+use log::{debug, warn};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+use std::str::FromStr;
+use itertools::Itertools;
+use regex::Regex;
+use rayon::prelude::*;
+
+use crate::precedence::{middle, Cycle, Order, PrecedenceRules};
+use crate::{AocError, ParseError, Solution};
+
+type Rules = PrecedenceRules<u64>;
+
+/// Rule that page `before` has to be printed before page `after`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rule {
+    pub before: u64,
+    pub after: u64,
+    /// 1-based input line the rule was read from.
+    pub line: usize,
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{} (line {})", self.before, self.after, self.line)
+    }
+}
+
+/// Parsed safety manual, the page ordering rules and the updates.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Manual {
+    /// Rules in input order, including repeated ones.
+    pub rules: Vec<Rule>,
+    /// The rules without the line numbers.
+    pub precedence: Rules,
+    pub updates: Vec<Vec<u64>>,
+}
+
+pub struct Problem5;
+
+impl Solution for Problem5 {
+    const DAY: u8 = 5;
+
+    type Input = Manual;
+    type Output = u64;
+
+    fn parse(input_string: &str) -> Result<Self::Input, AocError> {
+        parse_input(input_string)
+    }
+
+    fn part1(manual: &Self::Input) -> Result<Self::Output, AocError> {
+        let rules = &manual.precedence;
+        Ok(manual
+            .updates
+            .iter()
+            .filter(|order| rules.is_ordered(order))
+            .filter_map(|order| middle(order))
+            .sum::<u64>())
+    }
+
+    fn part2(manual: &Self::Input) -> Result<Self::Output, AocError> {
+        let rules = &manual.precedence;
+        let fixed_orders = manual
+            .updates
+            .par_iter()
+            .filter(|order| !rules.is_ordered(order))
+            .map(|order| {
+                let sorted =
+                    rules.topological_sort(order).map_err(|err| format!("Cannot order update {:?}: {}", order, err))?;
+                if let Some(ambiguity) = &sorted.ambiguity {
+                    warn!("Update {:?} has no unique order, using {:?}: Pages {}", order, sorted.items, ambiguity);
+                }
+                debug!("Sorted {:?} into {:?}", order, sorted.items);
+                Ok(sorted.items)
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map_err(|reason| AocError::Unsolvable { day: Self::DAY, part: 2, reason })?;
+        Ok(fixed_orders.par_iter().filter_map(|order| middle(order)).sum::<u64>())
+    }
+}
+
+fn parse_number(input_line: &str, line_index: usize, number_str: &str) -> Result<u64, ParseError> {
+    u64::from_str(number_str).map_err(|err| {
+        let column = number_str.as_ptr() as usize - input_line.as_ptr() as usize + 1;
+        ParseError::new(line_index + 1, column, format!("Invalid page number '{}': {}", number_str, err))
+    })
+}
+
+fn parse_input(input_string: &str) -> Result<Manual, AocError> {
+    let input_lines = input_string.lines();
+    let rules_regex = Regex::new(r"^(\d+)\|(\d+)$")?;
+    let order_regex = Regex::new(r"^((\d)+(,)?)+$")?;
+
+    let mut rules = Vec::new();
+    let mut precedence = Rules::default();
+    let mut orders = Vec::new();
+
+    for (line_index, input_line) in input_lines.enumerate() {
+        if rules_regex.is_match(input_line) {
+            let rules_split = input_line.split("|").collect::<Vec<&str>>();
+            let left = parse_number(input_line, line_index, rules_split[0])?;
+            let right = parse_number(input_line, line_index, rules_split[1])?;
+
+            precedence.insert(left, right);
+            rules.push(Rule { before: left, after: right, line: line_index + 1 });
+
+            continue;
+        }
+        if order_regex.is_match(input_line) {
+            let orders_split = input_line.split(",").collect::<Vec<&str>>();
+            let mut order = Vec::with_capacity(orders_split.len());
+            for order_str in orders_split {
+                let page = parse_number(input_line, line_index, order_str)?;
+                if let Some(first) = order.iter().position(|earlier| *earlier == page) {
+                    let column = order_str.as_ptr() as usize - input_line.as_ptr() as usize + 1;
+                    let reason = format!("Page {} appears twice in the update, first at index {}", page, first);
+                    return Err(ParseError::new(line_index + 1, column, reason).into());
+                }
+                order.push(page);
+            }
+            orders.push(order);
+        }
+    }
+
+    Ok(Manual { rules, precedence, updates: orders })
+}
+
+/// Pair of pages of an update printed in the wrong order.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Violation {
+    /// Page printed first, at index `first_index` of the update.
+    pub first: u64,
+    pub first_index: usize,
+    /// Page printed later, at index `second_index` of the update.
+    pub second: u64,
+    pub second_index: usize,
+    /// Rule requiring `second` to be printed before `first`.
+    pub rule: Rule,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (index {}) comes before {} (index {}), but rule {}|{} on line {} requires the opposite",
+            self.first,
+            self.first_index,
+            self.second,
+            self.second_index,
+            self.rule.before,
+            self.rule.after,
+            self.rule.line
+        )
+    }
+}
+
+/// Every pair of pages in `update` that breaks a rule, ordered by the indices of the pages.
+pub fn violations(manual: &Manual, update: &[u64]) -> Vec<Violation> {
+    let mut rules = HashMap::new();
+    for rule in &manual.rules {
+        rules.entry((rule.before, rule.after)).or_insert(*rule);
+    }
+
+    manual
+        .precedence
+        .violations(update)
+        .into_iter()
+        .map(|(first_index, second_index)| Violation {
+            first: update[first_index],
+            first_index,
+            second: update[second_index],
+            second_index,
+            rule: rules[&(update[second_index], update[first_index])],
+        })
+        .collect()
+}
+
+/// Lists the broken rules of every update in the wrong order.
+pub fn explain_violations(manual: &Manual) -> String {
+    let mut explanation = String::new();
+    for (index, update) in manual.updates.iter().enumerate() {
+        let violations = violations(manual, update);
+        if violations.is_empty() {
+            continue;
+        }
+        explanation.push_str(&format!("Update {} ({}):\n", index + 1, update.iter().join(",")));
+        for violation in violations {
+            explanation.push_str(&format!("  {}\n", violation));
+        }
+    }
+    explanation
+}
+
+/// Result of checking the rules for cycles and rules implied by others.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RuleAnalysis {
+    /// Strongly connected components containing a cycle, pages in ascending order.
+    pub components: Vec<Vec<u64>>,
+    /// A shortest cycle of every component as the rules along it.
+    pub cycles: Vec<Vec<Rule>>,
+    /// Rules between different components implied by other rules and repeated rules, in input order.
+    pub redundant: Vec<Rule>,
+    /// Order of the pages of every update, or the cycle preventing one.
+    pub updates: Vec<Result<Order<u64>, Cycle<u64>>>,
+}
+
+/// Pages after `page` in ascending order, so that the analysis does not depend on hash order.
+fn sorted_successors(successors: &Rules, page: u64) -> Vec<u64> {
+    let mut pages = successors.successors(&page).copied().collect::<Vec<_>>();
+    pages.sort();
+    pages
+}
+
+/// Tarjan's algorithm over the rule graph.
+struct Tarjan<'a> {
+    successors: &'a Rules,
+    indices: HashMap<u64, usize>,
+    low_links: HashMap<u64, usize>,
+    stack: Vec<u64>,
+    on_stack: HashSet<u64>,
+    components: Vec<Vec<u64>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, page: u64) {
+        let index = self.indices.len();
+        self.indices.insert(page, index);
+        self.low_links.insert(page, index);
+        self.stack.push(page);
+        self.on_stack.insert(page);
+
+        for successor in sorted_successors(self.successors, page) {
+            if !self.indices.contains_key(&successor) {
+                self.visit(successor);
+                let low_link = self.low_links[&page].min(self.low_links[&successor]);
+                self.low_links.insert(page, low_link);
+            } else if self.on_stack.contains(&successor) {
+                let low_link = self.low_links[&page].min(self.indices[&successor]);
+                self.low_links.insert(page, low_link);
+            }
+        }
+
+        if self.low_links[&page] == index {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(&member);
+                component.push(member);
+                if member == page {
+                    break;
+                }
+            }
+            component.sort();
+            self.components.push(component);
+        }
+    }
+}
+
+/// Strongly connected components of the rule graph.
+fn strongly_connected_components(successors: &Rules) -> Vec<Vec<u64>> {
+    let mut pages = successors.pairs().flat_map(|(before, after)| [*before, *after]).collect::<Vec<_>>();
+    pages.sort();
+    pages.dedup();
+
+    let mut tarjan = Tarjan {
+        successors,
+        indices: HashMap::new(),
+        low_links: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: Vec::new(),
+    };
+    for page in pages {
+        if !tarjan.indices.contains_key(&page) {
+            tarjan.visit(page);
+        }
+    }
+    tarjan.components
+}
+
+/// Pages along a shortest cycle through the pages of `component`, starting with the smallest page.
+fn shortest_cycle(successors: &Rules, component: &[u64]) -> Vec<u64> {
+    let members = component.iter().copied().collect::<HashSet<_>>();
+    let mut shortest: Option<Vec<u64>> = None;
+    for start in component {
+        let mut parents = HashMap::from([(*start, *start)]);
+        let mut queue = VecDeque::from([*start]);
+        let mut closing = None;
+        'search: while let Some(page) = queue.pop_front() {
+            for successor in sorted_successors(successors, page) {
+                if successor == *start {
+                    closing = Some(page);
+                    break 'search;
+                }
+                if members.contains(&successor) && !parents.contains_key(&successor) {
+                    parents.insert(successor, page);
+                    queue.push_back(successor);
+                }
+            }
+        }
+
+        let Some(mut page) = closing else {
+            continue;
+        };
+        let mut cycle = vec![page];
+        while page != *start {
+            page = parents[&page];
+            cycle.push(page);
+        }
+        cycle.reverse();
+        if shortest.as_ref().is_none_or(|shortest| cycle.len() < shortest.len()) {
+            shortest = Some(cycle);
+        }
+    }
+    shortest.unwrap_or_default()
+}
+
+/// Whether `to` can be reached from `from` without taking the rule `from|to` itself.
+fn implied<T: Hash + Eq + Copy>(successors: &PrecedenceRules<T>, from: T, to: T) -> bool {
+    let mut seen = HashSet::from([from]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(page) = queue.pop_front() {
+        for successor in successors.successors(&page) {
+            if page == from && *successor == to {
+                continue;
+            }
+            if *successor == to {
+                return true;
+            }
+            if seen.insert(*successor) {
+                queue.push_back(*successor);
+            }
+        }
+    }
+    false
+}
+
+/// Rules that can all be dropped without changing which pages have to come before others.
+///
+/// Within a cycle every page already has to come before every other one, so only rules between
+/// different strongly connected components can be redundant. Such a rule is redundant if another
+/// path connects its components in the condensation of the rule graph, or if it is not the first
+/// rule between the same two components. Repeated rules are always redundant.
+fn redundant_rules(manual: &Manual, components: &[Vec<u64>]) -> Vec<Rule> {
+    let component_of = components
+        .iter()
+        .enumerate()
+        .flat_map(|(index, component)| component.iter().map(move |page| (*page, index)))
+        .collect::<HashMap<_, _>>();
+    let condensation = PrecedenceRules::from_pairs(
+        manual
+            .precedence
+            .pairs()
+            .map(|(before, after)| (component_of[before], component_of[after]))
+            .filter(|(before, after)| before != after),
+    );
+
+    let mut seen_rules = HashSet::new();
+    let mut connected = HashSet::new();
+    let mut redundant = Vec::new();
+    for rule in &manual.rules {
+        let (before, after) = (component_of[&rule.before], component_of[&rule.after]);
+        let repeated = !seen_rules.insert((rule.before, rule.after));
+        if repeated
+            || (before != after && (!connected.insert((before, after)) || implied(&condensation, before, after)))
+        {
+            redundant.push(*rule);
+        }
+    }
+    redundant
+}
+
+/// Looks for cycles and redundant rules and checks whether every update has a unique order.
+pub fn analyze_rules(manual: &Manual) -> RuleAnalysis {
+    let successors = &manual.precedence;
+    // Collected in reverse so that the first of repeated rules is kept.
+    let first_rules =
+        manual.rules.iter().rev().map(|rule| ((rule.before, rule.after), *rule)).collect::<HashMap<_, _>>();
+
+    let all_components = strongly_connected_components(successors);
+    let redundant = redundant_rules(manual, &all_components);
+    let components = all_components
+        .into_iter()
+        .filter(|component| {
+            component.len() > 1 || successors.requires(&component[0], &component[0])
+        })
+        .collect::<Vec<_>>();
+    let cycles = components
+        .iter()
+        .map(|component| {
+            let cycle = shortest_cycle(successors, component);
+            cycle
+                .iter()
+                .zip(cycle.iter().cycle().skip(1))
+                .map(|(before, after)| first_rules[&(*before, *after)])
+                .collect()
+        })
+        .collect();
+
+    let updates = manual.updates.iter().map(|update| successors.topological_sort(update)).collect();
+    RuleAnalysis { components, cycles, redundant, updates }
+}
+
+/// Text format of an exported rule graph.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    Dot,
+    Mermaid,
+}
+
+/// Renders the rule graph, restricted to the pages of `update` if one is given.
+///
+/// Pages appear in update order, otherwise in ascending order. Rules broken by the order of
+/// `update` are drawn in red.
+pub fn rule_graph(manual: &Manual, update: Option<&[u64]>, format: GraphFormat) -> String {
+    let positions = update.map(|update| {
+        update.iter().enumerate().map(|(index, page)| (*page, index)).collect::<HashMap<_, _>>()
+    });
+    let pages = match update {
+        Some(update) => update.to_vec(),
+        None => {
+            let mut pages = manual.rules.iter().flat_map(|rule| [rule.before, rule.after]).collect::<Vec<_>>();
+            pages.sort();
+            pages.dedup();
+            pages
+        }
+    };
+
+    let mut edges = manual
+        .rules
+        .iter()
+        .map(|rule| (rule.before, rule.after))
+        .filter(|(before, after)| {
+            positions.as_ref().is_none_or(|positions| positions.contains_key(before) && positions.contains_key(after))
+        })
+        .collect::<Vec<_>>();
+    edges.sort();
+    edges.dedup();
+    let violated = |(before, after): (u64, u64)| {
+        positions.as_ref().is_some_and(|positions| positions[&before] > positions[&after])
+    };
+
+    let mut graph = String::new();
+    match format {
+        GraphFormat::Dot => {
+            graph.push_str("digraph rules {\n    rankdir=LR;\n");
+            for page in &pages {
+                graph.push_str(&format!("    {};\n", page));
+            }
+            for (before, after) in &edges {
+                let style = if violated((*before, *after)) { " [color=red]" } else { "" };
+                graph.push_str(&format!("    {} -> {}{};\n", before, after, style));
+            }
+            graph.push_str("}\n");
+        }
+        GraphFormat::Mermaid => {
+            graph.push_str("flowchart LR\n");
+            for page in &pages {
+                graph.push_str(&format!("    p{}[{}]\n", page, page));
+            }
+            for (before, after) in &edges {
+                graph.push_str(&format!("    p{} --> p{}\n", before, after));
+            }
+            let violated_links = edges
+                .iter()
+                .enumerate()
+                .filter(|(_, edge)| violated(**edge))
+                .map(|(index, _)| index.to_string())
+                .collect::<Vec<_>>();
+            if !violated_links.is_empty() {
+                graph.push_str(&format!("    linkStyle {} stroke:red\n", violated_links.join(",")));
+            }
+        }
+    }
+    graph
+}
+
+/// Writes the graph rendered by [`rule_graph`] to `path`.
+pub fn save_rule_graph(
+    manual: &Manual,
+    update: Option<&[u64]>,
+    format: GraphFormat,
+    path: impl AsRef<Path>,
+) -> Result<(), AocError> {
+    let path = path.as_ref();
+    fs::write(path, rule_graph(manual, update, format))
+        .map_err(|source| AocError::Write { path: path.to_path_buf(), source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        analyze_rules, explain_violations, rule_graph, save_rule_graph, violations, GraphFormat, Problem5, Rule,
+        Violation,
+    };
+    use crate::precedence::{Ambiguity, Cycle, Order};
+    use crate::{AocError, Solution};
+
+    #[test]
+    fn test_example() {
+        let input_string = include_str!("../../problems/problem5_test.txt");
+        let input = Problem5::parse(input_string).unwrap();
+
+        assert_eq!(Problem5::part1(&input).unwrap(), 143);
+        assert_eq!(Problem5::part2(&input).unwrap(), 123);
+    }
+
+    #[test]
+    fn test_sort_pages() {
+        let input_string = include_str!("../../problems/problem5_test.txt");
+        let rules = Problem5::parse(input_string).unwrap().precedence;
+
+        let order = rules.topological_sort(&[97, 13, 75, 29, 47]).unwrap();
+        assert_eq!(order, Order { items: vec![97, 75, 47, 29, 13], ambiguity: None });
+        let mut pages = [97, 13, 75, 29, 47];
+        rules.sort(&mut pages).unwrap();
+        assert_eq!(pages, [97, 75, 47, 29, 13]);
+        assert_eq!(
+            rules.topological_sort(&[97, 13, 42]).unwrap(),
+            Order { items: vec![97, 13, 42], ambiguity: Some(Ambiguity { position: 0, candidates: vec![97, 42] }) }
+        );
+
+        let input = Problem5::parse("1|2\n2|3\n3|1\n3|4\n\n4,1,2,3\n").unwrap();
+        assert_eq!(
+            Problem5::part2(&input).unwrap_err().to_string(),
+            "Day 5 part 2 has no solution: Cannot order update [4, 1, 2, 3]: \
+             Rules between 1 -> 2 -> 3 -> 1 form a cycle"
+        );
+
+        assert!(matches!(
+            Problem5::parse("1|2\n\n2,1,2\n").unwrap_err(),
+            AocError::Parse { line: 3, column: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn test_analyze_rules() {
+        let manual = Problem5::parse("1|2\n2|3\n3|1\n1|3\n3|4\n1|4\n1|2\n\n1,2,4\n3,4\n1,2,3\n").unwrap();
+        let rule = |before, after, line| Rule { before, after, line };
+
+        let analysis = analyze_rules(&manual);
+        assert_eq!(analysis.components, vec![vec![1, 2, 3]]);
+        assert_eq!(analysis.cycles, vec![vec![rule(1, 3, 4), rule(3, 1, 3)]]);
+        assert_eq!(analysis.redundant, vec![rule(1, 4, 6), rule(1, 2, 7)]);
+        assert_eq!(
+            analysis.updates,
+            vec![
+                Ok(Order {
+                    items: vec![1, 2, 4],
+                    ambiguity: Some(Ambiguity { position: 1, candidates: vec![2, 4] }),
+                }),
+                Ok(Order { items: vec![3, 4], ambiguity: None }),
+                Err(Cycle { items: vec![1, 3] }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redundant_rules_between_cycles() {
+        let manual = Problem5::parse("1|2\n2|1\n2|3\n3|4\n4|3\n1|4\n5|1\n5|3\n6|5\n\n1,2\n").unwrap();
+        let rule = |before, after, line| Rule { before, after, line };
+
+        let analysis = analyze_rules(&manual);
+        assert_eq!(analysis.components, vec![vec![3, 4], vec![1, 2]]);
+        assert_eq!(analysis.redundant, vec![rule(1, 4, 6), rule(5, 3, 8)]);
+    }
+
+    #[test]
+    fn test_rule_graph() {
+        let manual = Problem5::parse("1|2\n2|3\n4|1\n\n3,1,2\n").unwrap();
+
+        assert_eq!(
+            rule_graph(&manual, Some(&manual.updates[0]), GraphFormat::Dot),
+            "digraph rules {\n    rankdir=LR;\n    3;\n    1;\n    2;\n    1 -> 2;\n    2 -> 3 [color=red];\n}\n"
+        );
+        assert_eq!(
+            rule_graph(&manual, Some(&manual.updates[0]), GraphFormat::Mermaid),
+            "flowchart LR\n    p3[3]\n    p1[1]\n    p2[2]\n    p1 --> p2\n    p2 --> p3\n    linkStyle 1 stroke:red\n"
+        );
+        assert!(rule_graph(&manual, None, GraphFormat::Dot).contains("    4 -> 1;\n"));
+    }
+
+    #[test]
+    fn test_save_rule_graph_to_missing_directory() {
+        let manual = Problem5::parse("1|2\n\n1,2\n").unwrap();
+        let err = save_rule_graph(&manual, None, GraphFormat::Dot, "/nonexistent/dir/rules.dot").unwrap_err();
+        assert!(matches!(err, AocError::Write { .. }));
+        assert_eq!(err.exit_code(), 9);
+    }
+
+    #[test]
+    fn test_violations() {
+        let input_string = include_str!("../../problems/problem5_test.txt");
+        let manual = Problem5::parse(input_string).unwrap();
+
+        assert!(violations(&manual, &manual.updates[0]).is_empty());
+        assert_eq!(
+            violations(&manual, &manual.updates[4]),
+            vec![Violation {
+                first: 13,
+                first_index: 1,
+                second: 29,
+                second_index: 2,
+                rule: Rule { before: 29, after: 13, line: 8 },
+            }]
+        );
+        assert_eq!(violations(&manual, &manual.updates[5]).len(), 4);
+
+        let explanation = explain_violations(&manual);
+        assert_eq!(explanation.lines().filter(|line| line.starts_with("Update")).count(), 3);
+        assert!(explanation.contains(
+            "Update 4 (75,97,47,61,53):\n  \
+             75 (index 0) comes before 97 (index 1), but rule 97|75 on line 16 requires the opposite\n"
+        ));
+    }
+}
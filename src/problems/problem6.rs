@@ -0,0 +1,467 @@
+use std::collections::HashSet;
+
+use crossterm::style::Stylize;
+use log::debug;
+use rayon::prelude::*;
+
+use crate::grid::{Direction, Grid, Pos};
+use crate::render::{Color, Image, Overlay, Palette};
+use crate::{AocError, ParseError, Solution};
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Guard {
+    pub pos: Pos,
+    pub direction: Direction,
+}
+
+impl Default for Guard {
+    fn default() -> Self {
+        Guard {
+            pos: Pos::default(),
+            direction: Direction::Up,
+        }
+    }
+}
+
+/// Why a patrol ended.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Termination {
+    /// The guard walked off the map.
+    Exited,
+    /// The guard reached a position and direction it had before and will walk in circles forever.
+    Loop,
+}
+
+/// Outcome of a complete patrol.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PatrolResult {
+    /// Distinct cells the guard stood on in the order of the first visit.
+    pub visited: Vec<Pos>,
+    /// Every state of the guard starting with the initial one, a turn is a step of its own.
+    pub trace: Vec<Guard>,
+    pub termination: Termination,
+}
+
+/// Walks the guard over the map, turning right in front of obstacles.
+///
+/// Iterating yields every state after the initial one and ends when the guard leaves the
+/// map, so it never ends for a guard stuck in a loop. [`Patrol::run`] detects loops.
+#[derive(Debug, Clone)]
+pub struct Patrol<'a> {
+    map: &'a Grid<bool>,
+    guard: Guard,
+}
+
+impl<'a> Patrol<'a> {
+    pub fn new(map: &'a Grid<bool>, guard: Guard) -> Self {
+        Patrol { map, guard }
+    }
+
+    pub fn guard(&self) -> Guard {
+        self.guard
+    }
+
+    /// Walks until the guard leaves the map or repeats a state.
+    pub fn run(self) -> PatrolResult {
+        // Bit set of the directions the guard had on every cell.
+        let mut seen = Grid::from_elem(self.map.rows(), self.map.cols(), 0u8);
+        let direction_bit = |direction: Direction| {
+            1u8 << Direction::ALL.iter().position(|d| *d == direction).expect("Every direction is listed")
+        };
+
+        let mut visited = vec![self.guard.pos];
+        let mut trace = vec![self.guard];
+        seen[self.guard.pos] |= direction_bit(self.guard.direction);
+
+        for guard in self {
+            trace.push(guard);
+            let state = &mut seen[guard.pos];
+            if *state & direction_bit(guard.direction) != 0 {
+                return PatrolResult { visited, trace, termination: Termination::Loop };
+            }
+            if *state == 0 {
+                visited.push(guard.pos);
+            }
+            *state |= direction_bit(guard.direction);
+        }
+        PatrolResult { visited, trace, termination: Termination::Exited }
+    }
+}
+
+impl Iterator for Patrol<'_> {
+    type Item = Guard;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_pos = self.map.offset(self.guard.pos, self.guard.direction)?;
+        if self.map[next_pos] {
+            self.guard.direction = self.guard.direction.rotate_right();
+        } else {
+            self.guard.pos = next_pos;
+        }
+        Some(self.guard)
+    }
+}
+
+pub struct Problem6;
+
+impl Solution for Problem6 {
+    const DAY: u8 = 6;
+
+    type Input = (Grid<bool>, Guard);
+    type Output = usize;
+
+    fn parse(input_string: &str) -> Result<Self::Input, AocError> {
+        Ok(parse_map(input_string, &Glyphs::default())?)
+    }
+
+    fn part1((map, start_position): &Self::Input) -> Result<Self::Output, AocError> {
+        let patrol = Patrol::new(map, *start_position).run();
+        if patrol.termination == Termination::Loop {
+            return Err(AocError::Unsolvable {
+                day: Self::DAY,
+                part: 1,
+                reason: "The guard never leaves the map".to_string(),
+            });
+        }
+
+        debug!("\n{}", visualize_path(map, &patrol.visited));
+        Ok(patrol.visited.len())
+    }
+
+    fn part2((map, start_position): &Self::Input) -> Result<Self::Output, AocError> {
+        let patrol = Patrol::new(map, *start_position).run();
+        if patrol.termination == Termination::Loop {
+            return Err(AocError::Unsolvable {
+                day: Self::DAY,
+                part: 2,
+                reason: "The guard is already stuck in a loop without a new obstruction".to_string(),
+            });
+        }
+
+        Ok(find_loop_obstructions(map, *start_position, &patrol.visited).len())
+    }
+}
+
+/// Index of an orthogonal direction in [`Direction::ORTHOGONAL`].
+fn direction_index(direction: Direction) -> usize {
+    Direction::ORTHOGONAL
+        .iter()
+        .position(|d| *d == direction)
+        .expect("Guards only walk in orthogonal directions")
+}
+
+/// Cell in front of the next obstacle for every cell and direction.
+///
+/// Lets a guard move from one turn to the next in a single lookup instead of walking every cell.
+#[derive(Debug, Clone)]
+struct JumpTable {
+    /// Indexed by [`direction_index`], `None` if the guard walks off the map.
+    stops: [Grid<Option<Pos>>; 4],
+}
+
+impl JumpTable {
+    fn new(map: &Grid<bool>) -> Self {
+        let stops = Direction::ORTHOGONAL.map(|direction| {
+            let mut stops = Grid::from_elem(map.rows(), map.cols(), None);
+            let delta = direction.delta();
+            // Cells are visited so that the neighbor in `direction` is always done first.
+            let ordered = |len: usize, step: isize| -> Vec<usize> {
+                if step > 0 {
+                    (0..len).rev().collect()
+                } else {
+                    (0..len).collect()
+                }
+            };
+            for row in ordered(map.rows(), delta.d_row) {
+                for col in ordered(map.cols(), delta.d_col) {
+                    let pos = Pos::new(row, col);
+                    stops[pos] = match map.offset(pos, direction) {
+                        None => None,
+                        Some(next) if map[next] => Some(pos),
+                        Some(next) => stops[next],
+                    };
+                }
+            }
+            stops
+        });
+        JumpTable { stops }
+    }
+
+    /// Cell the guard stops at before turning, taking an `extra` obstacle into account.
+    fn stop(&self, guard: Guard, extra: Pos) -> Option<Pos> {
+        let stop = self.stops[direction_index(guard.direction)][guard.pos];
+        let pos = guard.pos;
+        // Distance of `extra` along the walking direction if it lies ahead of the guard.
+        let extra_distance = match guard.direction {
+            Direction::Up if extra.col == pos.col && extra.row < pos.row => pos.row - extra.row,
+            Direction::Down if extra.col == pos.col && extra.row > pos.row => extra.row - pos.row,
+            Direction::Left if extra.row == pos.row && extra.col < pos.col => pos.col - extra.col,
+            Direction::Right if extra.row == pos.row && extra.col > pos.col => extra.col - pos.col,
+            _ => return stop,
+        };
+        let stop_distance = stop.map(|stop| stop.row.abs_diff(pos.row) + stop.col.abs_diff(pos.col));
+        if stop_distance.is_none_or(|stop_distance| extra_distance <= stop_distance) {
+            pos.offset(guard.direction.delta() * (extra_distance as isize - 1))
+        } else {
+            stop
+        }
+    }
+
+    /// Whether the guard starting as `guard` ends up in a loop with an `extra` obstacle.
+    fn loops(&self, mut guard: Guard, extra: Pos) -> bool {
+        let mut turns = HashSet::new();
+        while let Some(pos) = self.stop(guard, extra) {
+            guard = Guard { pos, direction: guard.direction.rotate_right() };
+            if !turns.insert(guard) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Cells of `path` where a new obstacle traps the guard in a loop.
+///
+/// Only cells the guard walks over can change its route, the start is excluded as the guard
+/// would notice the obstacle being placed.
+fn find_loop_obstructions(map: &Grid<bool>, guard: Guard, path: &[Pos]) -> Vec<Pos> {
+    let jump_table = JumpTable::new(map);
+    path.par_iter()
+        .filter(|pos| **pos != guard.pos && jump_table.loops(guard, **pos))
+        .copied()
+        .collect()
+}
+
+/// Draws obstacles as `#` and the cells of `path` as `X`.
+fn visualize_path(map: &Grid<bool>, path: &[Pos]) -> String {
+    let mut grid = map.map(|obstacle| if *obstacle { '#' } else { '.' });
+    for pos in path {
+        grid[*pos] = 'X';
+    }
+    grid.to_string()
+}
+
+/// Colored map with obstacles, the cells visited so far and the guard.
+fn render_frame(map: &Grid<bool>, visited: &Grid<bool>, guard: Guard) -> String {
+    let mut frame = String::new();
+    for row in 0..map.rows() {
+        for col in 0..map.cols() {
+            let pos = Pos::new(row, col);
+            let cell = if pos == guard.pos {
+                Glyphs::default().guard(guard.direction).red().bold()
+            } else if map[pos] {
+                '#'.dark_grey()
+            } else if visited[pos] {
+                'X'.yellow()
+            } else {
+                '.'.stylize()
+            };
+            frame.push_str(&cell.to_string());
+        }
+        frame.push('\n');
+    }
+    frame
+}
+
+/// Frames of the patrol for a [`Player`](crate::animation::Player), one per state of the guard.
+pub fn animation_frames(map: &Grid<bool>, guard: Guard) -> impl Iterator<Item = String> + '_ {
+    let patrol = Patrol::new(map, guard).run();
+    let steps = patrol.trace.len() - 1;
+    let ending = match patrol.termination {
+        Termination::Exited => "the guard leaves the map",
+        Termination::Loop => "the guard is stuck in a loop",
+    };
+
+    let mut visited = map.map(|_| false);
+    let mut visited_count = 0;
+    patrol.trace.into_iter().enumerate().map(move |(step, guard)| {
+        if !visited[guard.pos] {
+            visited[guard.pos] = true;
+            visited_count += 1;
+        }
+        let status = if step == steps { format!(", {}", ending) } else { String::new() };
+        format!(
+            "{}Step {}/{}, {} cells visited{}",
+            render_frame(map, &visited, guard),
+            step,
+            steps,
+            visited_count,
+            status
+        )
+    })
+}
+
+/// Picture of the patrol with the visited cells, the route of the guard and the start marked.
+pub fn patrol_image(map: &Grid<bool>, guard: Guard) -> Image<'_, bool> {
+    let patrol = Patrol::new(map, guard).run();
+    let mut route = patrol.trace.iter().map(|guard| guard.pos).collect::<Vec<_>>();
+    route.dedup();
+
+    Image::new(map, Palette::new(Color::rgb(240, 240, 240)).with(true, Color::rgb(66, 66, 66)))
+        .overlay(Overlay::Highlight { cells: patrol.visited, color: Color::rgb(255, 224, 130) })
+        .overlay(Overlay::Path { cells: route, color: Color::rgb(25, 118, 210) })
+        .overlay(Overlay::Outline { cells: vec![guard.pos], color: Color::rgb(211, 47, 47) })
+}
+
+/// Characters of the cells of a map.
+///
+/// The glyphs are expected to be distinct, a character used twice is read as the first cell
+/// kind in field order.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Glyphs {
+    pub obstacle: char,
+    pub empty: char,
+    /// Guard looking up, right, down and left.
+    pub guards: [char; 4],
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs {
+            obstacle: '#',
+            empty: '.',
+            guards: ['^', '>', 'v', '<'],
+        }
+    }
+}
+
+impl Glyphs {
+    /// Glyph of a guard looking in `direction`.
+    pub fn guard(&self, direction: Direction) -> char {
+        self.guards[direction_index(direction)]
+    }
+
+    /// Direction of the guard drawn as `c`.
+    fn guard_direction(&self, c: char) -> Option<Direction> {
+        self.guards.iter().position(|glyph| *glyph == c).map(|index| Direction::ORTHOGONAL[index])
+    }
+}
+
+/// Parses the map into a grid of obstacles and the starting guard.
+///
+/// Fails on characters that are not in `glyphs` and on maps without exactly one guard.
+pub fn parse_map(input_string: &str, glyphs: &Glyphs) -> Result<(Grid<bool>, Guard), ParseError> {
+    let mut start_position: Option<Guard> = None;
+
+    let map = Grid::parse(input_string, |pos, c| {
+        if c == glyphs.obstacle {
+            return Ok(true);
+        }
+        if c == glyphs.empty {
+            return Ok(false);
+        }
+        let Some(direction) = glyphs.guard_direction(c) else {
+            return Err(format!("Invalid char '{}'", c));
+        };
+        if let Some(first) = start_position {
+            return Err(format!(
+                "Second guard '{}', the first one is at {}:{}",
+                c,
+                first.pos.row + 1,
+                first.pos.col + 1
+            ));
+        }
+        start_position = Some(Guard { pos, direction });
+        Ok(false)
+    })?;
+
+    let start_position = start_position.ok_or_else(|| {
+        ParseError::at_offset(input_string, input_string.trim_end().len(), "No guard on the map")
+    })?;
+    Ok((map, start_position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        animation_frames, find_loop_obstructions, parse_map, visualize_path, Glyphs, Guard, Patrol, Problem6,
+        Termination,
+    };
+    use crate::grid::{Direction, Pos};
+    use crate::{AocError, ParseError, Solution};
+
+    #[test]
+    fn test_example() {
+        let input_string = include_str!("../../problems/problem6_test.txt");
+        let (map, start_position) = Problem6::parse(input_string).unwrap();
+        let patrol = Patrol::new(&map, start_position).run();
+
+        assert_eq!(patrol.termination, Termination::Exited);
+        assert_eq!(patrol.visited.len(), 41);
+        let path = visualize_path(&map, &patrol.visited);
+        assert_eq!(path.chars().filter(|c| *c == 'X').count(), 41);
+        assert_eq!(path.chars().filter(|c| *c == '#').count(), 8);
+        assert_eq!(patrol.trace[1], Guard { pos: Pos::new(5, 4), direction: Direction::Up });
+        assert_eq!(patrol.trace.last().unwrap().pos, Pos::new(9, 7));
+    }
+
+    #[test]
+    fn test_example_part2() {
+        let input_string = include_str!("../../problems/problem6_test.txt");
+        let (map, start_position) = Problem6::parse(input_string).unwrap();
+        let path = Patrol::new(&map, start_position).run().visited;
+
+        let mut obstructions = find_loop_obstructions(&map, start_position, &path);
+        obstructions.sort();
+
+        // Same placements as simulating the full patrol on a map with the obstacle added.
+        let mut expected = path[1..]
+            .iter()
+            .copied()
+            .filter(|pos| {
+                let mut obstructed = map.clone();
+                obstructed[*pos] = true;
+                Patrol::new(&obstructed, start_position).run().termination == Termination::Loop
+            })
+            .collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(obstructions, expected);
+        assert_eq!(obstructions.len(), 6);
+    }
+
+    #[test]
+    fn test_animation_frames() {
+        let input_string = include_str!("../../problems/problem6_test.txt");
+        let (map, start_position) = Problem6::parse(input_string).unwrap();
+
+        let frames = animation_frames(&map, start_position).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 55);
+        assert!(frames[0].ends_with("Step 0/54, 1 cells visited"));
+        assert!(frames[54].ends_with("Step 54/54, 41 cells visited, the guard leaves the map"));
+    }
+
+    #[test]
+    fn test_parse_map() {
+        let (map, guard) = Problem6::parse("..#\n.v.\n").unwrap();
+        assert!(map[Pos::new(0, 2)]);
+        assert_eq!(guard, Guard { pos: Pos::new(1, 1), direction: Direction::Down });
+
+        let glyphs = Glyphs { obstacle: 'O', empty: ' ', guards: ['N', 'E', 'S', 'W'] };
+        let (map, guard) = parse_map("O  \n  W\n", &glyphs).unwrap();
+        assert!(map[Pos::new(0, 0)]);
+        assert_eq!(guard, Guard { pos: Pos::new(1, 2), direction: Direction::Left });
+
+        let default = Glyphs::default();
+        assert_eq!(parse_map("..#\n.V.\n", &default).unwrap_err(), ParseError::new(2, 2, "Invalid char 'V'"));
+        assert_eq!(
+            parse_map("^.#\n..<\n", &default).unwrap_err(),
+            ParseError::new(2, 3, "Second guard '<', the first one is at 1:1")
+        );
+        assert_eq!(parse_map("..#\n...\n", &default).unwrap_err(), ParseError::new(2, 4, "No guard on the map"));
+        assert!(matches!(
+            Problem6::parse("..#\n.x^\n").unwrap_err(),
+            AocError::Parse { line: 2, column: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_loop() {
+        let input_string = ".#...\n....#\n.^...\n#....\n...#.\n";
+        let (map, start_position) = Problem6::parse(input_string).unwrap();
+        let patrol = Patrol::new(&map, start_position).run();
+
+        assert_eq!(patrol.termination, Termination::Loop);
+        assert_eq!(patrol.visited.len(), 8);
+        assert!(Problem6::part1(&(map, start_position)).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::problems::problem1::Problem1;
+use crate::problems::problem2::Problem2;
+use crate::problems::problem3::Problem3;
+use crate::problems::problem4::Problem4;
+use crate::problems::problem5::Problem5;
+use crate::problems::problem6::Problem6;
+use crate::{AocError, Solution};
+
+/// Object safe view on a [`Solution`] so that all days can be stored in one registry.
+///
+/// The parsed input is passed around as `dyn Any` and downcast back to `S::Input` by the part.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError>;
+
+    /// Solves `part` on an input previously returned by [`DynSolution::parse`].
+    ///
+    /// Panics if `input` was produced by another solution or `part` is neither 1 nor 2.
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<String, AocError>;
+}
+
+struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S> DynSolution for Registered<S>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<String, AocError> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was not parsed by this solution");
+        match part {
+            1 => S::part1(input).map(|answer| answer.to_string()),
+            2 => S::part2(input).map(|answer| answer.to_string()),
+            _ => panic!("Invalid part {}", part),
+        }
+    }
+}
+
+const SOLUTIONS: &[&dyn DynSolution] = &[
+    &Registered::<Problem1>(PhantomData),
+    &Registered::<Problem2>(PhantomData),
+    &Registered::<Problem3>(PhantomData),
+    &Registered::<Problem4>(PhantomData),
+    &Registered::<Problem5>(PhantomData),
+    &Registered::<Problem6>(PhantomData),
+];
+
+/// All registered solutions ordered by day.
+pub fn solutions() -> &'static [&'static dyn DynSolution] {
+    SOLUTIONS
+}
+
+/// Solution registered for `day`.
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().find(|solution| solution.day() == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, solutions};
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(5).map(|solution| solution.day()), Some(5));
+        assert!(find(25).is_none());
+        assert!(solutions().windows(2).all(|w| w[0].day() < w[1].day()));
+    }
+
+    #[test]
+    fn test_solve_erased() {
+        let solution = find(3).unwrap();
+        let input = solution.parse("mul(2,4)don't()mul(5,5)").unwrap();
+
+        assert_eq!(solution.solve(input.as_ref(), 1).unwrap(), "33");
+        assert_eq!(solution.solve(input.as_ref(), 2).unwrap(), "8");
+    }
+}
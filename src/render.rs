@@ -0,0 +1,451 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::grid::{Grid, Pos};
+use crate::AocError;
+
+/// 24 bit RGB color, written as `#rrggbb`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(color: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid color '{}', expected '#rrggbb'", color);
+        let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6 && hex.is_ascii()).ok_or_else(invalid)?;
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| invalid());
+        Ok(Color::rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Colors of the cell values, values without a color of their own use the fallback.
+#[derive(Debug, Clone)]
+pub struct Palette<T> {
+    colors: HashMap<T, Color>,
+    fallback: Color,
+}
+
+impl<T: Hash + Eq> Palette<T> {
+    pub fn new(fallback: Color) -> Self {
+        Palette { colors: HashMap::new(), fallback }
+    }
+
+    /// Returns the palette with `value` drawn in `color`.
+    pub fn with(mut self, value: T, color: Color) -> Self {
+        self.colors.insert(value, color);
+        self
+    }
+
+    pub fn color(&self, value: &T) -> Color {
+        self.colors.get(value).copied().unwrap_or(self.fallback)
+    }
+}
+
+/// Shape drawn on top of the cells.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Overlay {
+    /// Fills the cells.
+    Highlight { cells: Vec<Pos>, color: Color },
+    /// Line through the centers of the cells in order.
+    Path { cells: Vec<Pos>, color: Color },
+    /// Border around the area covered by the cells.
+    Outline { cells: Vec<Pos>, color: Color },
+}
+
+/// File format of an exported image.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ImageFormat {
+    /// Binary portable pixmap (`P6`).
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Format matching the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Edge of a cell, shared with the neighbor on that side.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Edge {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl Edge {
+    const ALL: [Edge; 4] = [Edge::Top, Edge::Right, Edge::Bottom, Edge::Left];
+}
+
+/// Edges of `cells` not shared with another cell of `cells`.
+fn outline_edges(cells: &[Pos]) -> Vec<(Pos, Edge)> {
+    let members = cells.iter().copied().collect::<HashSet<_>>();
+    let contains = |row: Option<usize>, col: Option<usize>| match (row, col) {
+        (Some(row), Some(col)) => members.contains(&Pos::new(row, col)),
+        _ => false,
+    };
+    let mut seen = HashSet::new();
+    let mut edges = Vec::new();
+    for pos in cells.iter().filter(|pos| seen.insert(**pos)) {
+        for edge in Edge::ALL {
+            let (row, col) = (Some(pos.row), Some(pos.col));
+            let shared = match edge {
+                Edge::Top => contains(pos.row.checked_sub(1), col),
+                Edge::Right => contains(row, pos.col.checked_add(1)),
+                Edge::Bottom => contains(pos.row.checked_add(1), col),
+                Edge::Left => contains(row, pos.col.checked_sub(1)),
+            };
+            if !shared {
+                edges.push((*pos, edge));
+            }
+        }
+    }
+    edges
+}
+
+/// RGB pixel buffer, row by row.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas { width, height, pixels: vec![Color::default(); width * height] }
+    }
+
+    /// Fills the rectangle, clipping the parts outside of the canvas.
+    fn fill(&mut self, x: isize, y: isize, width: usize, height: usize, color: Color) {
+        let clip = |start: isize, len: usize, max: usize| {
+            (start.clamp(0, max as isize) as usize)..((start + len as isize).clamp(0, max as isize) as usize)
+        };
+        for row in clip(y, height, self.height) {
+            for col in clip(x, width, self.width) {
+                self.pixels[row * self.width + col] = color;
+            }
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|color| [color.r, color.g, color.b]).collect()
+    }
+}
+
+/// Picture of a grid with overlays, exported as raster image or SVG.
+///
+/// Every cell is a square of `cell_size` pixels (or SVG units) filled with the color of its
+/// value, overlays are drawn in the order they were added.
+#[derive(Debug, Clone)]
+pub struct Image<'a, T> {
+    grid: &'a Grid<T>,
+    palette: Palette<T>,
+    cell_size: usize,
+    overlays: Vec<Overlay>,
+}
+
+impl<'a, T: Hash + Eq> Image<'a, T> {
+    pub fn new(grid: &'a Grid<T>, palette: Palette<T>) -> Self {
+        Image { grid, palette, cell_size: 8, overlays: Vec::new() }
+    }
+
+    /// Returns the image with cells of `cell_size` pixels, at least one.
+    pub fn cell_size(mut self, cell_size: usize) -> Self {
+        self.cell_size = cell_size.max(1);
+        self
+    }
+
+    /// Returns the image with `overlay` drawn on top of the previous ones.
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.cols() * self.cell_size
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.rows() * self.cell_size
+    }
+
+    /// Width of path and outline strokes.
+    fn stroke_width(&self) -> usize {
+        (self.cell_size / 4).max(1)
+    }
+
+    fn rasterize(&self) -> Canvas {
+        let size = self.cell_size;
+        let stroke = self.stroke_width();
+        let corner = |pos: Pos| ((pos.col * size) as isize, (pos.row * size) as isize);
+        let mut canvas = Canvas::new(self.width(), self.height());
+
+        for (pos, value) in self.grid.iter() {
+            let (x, y) = corner(pos);
+            canvas.fill(x, y, size, size, self.palette.color(value));
+        }
+        for overlay in &self.overlays {
+            match overlay {
+                Overlay::Highlight { cells, color } => {
+                    for pos in cells {
+                        let (x, y) = corner(*pos);
+                        canvas.fill(x, y, size, size, *color);
+                    }
+                }
+                Overlay::Path { cells, color } => {
+                    // Strokes are squares moved along the line between consecutive centers.
+                    let center = |pos: &Pos| {
+                        let (x, y) = corner(*pos);
+                        (x + (size / 2) as isize, y + (size / 2) as isize)
+                    };
+                    let offset = (stroke / 2) as isize;
+                    for (from, to) in cells.iter().map(center).zip(cells.iter().skip(1).map(center)) {
+                        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).max(1);
+                        for step in 0..=steps {
+                            let x = from.0 + (to.0 - from.0) * step / steps;
+                            let y = from.1 + (to.1 - from.1) * step / steps;
+                            canvas.fill(x - offset, y - offset, stroke, stroke, *color);
+                        }
+                    }
+                    if let [single] = cells.as_slice() {
+                        let (x, y) = center(single);
+                        canvas.fill(x - offset, y - offset, stroke, stroke, *color);
+                    }
+                }
+                Overlay::Outline { cells, color } => {
+                    for (pos, edge) in outline_edges(cells) {
+                        let (x, y) = corner(pos);
+                        let far = size as isize - stroke as isize;
+                        match edge {
+                            Edge::Top => canvas.fill(x, y, size, stroke, *color),
+                            Edge::Right => canvas.fill(x + far, y, stroke, size, *color),
+                            Edge::Bottom => canvas.fill(x, y + far, size, stroke, *color),
+                            Edge::Left => canvas.fill(x, y, stroke, size, *color),
+                        }
+                    }
+                }
+            }
+        }
+        canvas
+    }
+
+    /// Binary PPM (`P6`) image.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let canvas = self.rasterize();
+        let mut ppm = format!("P6\n{} {}\n255\n", canvas.width, canvas.height).into_bytes();
+        ppm.extend(canvas.bytes());
+        ppm
+    }
+
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let canvas = self.rasterize();
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, canvas.width as u32, canvas.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&canvas.bytes())?;
+        Ok(png)
+    }
+
+    /// SVG image, runs of cells with the same color in a row are merged into one rectangle.
+    pub fn to_svg(&self) -> String {
+        let size = self.cell_size;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+             shape-rendering=\"crispEdges\">\n",
+            w = self.width(),
+            h = self.height()
+        );
+        // Writing to a String cannot fail.
+        let rect = |svg: &mut String, pos: Pos, cols: usize, color: Color| {
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                pos.col * size,
+                pos.row * size,
+                cols * size,
+                size,
+                color
+            );
+        };
+
+        for row in 0..self.grid.rows() {
+            let mut start = 0;
+            for col in 1..=self.grid.cols() {
+                let color = self.palette.color(&self.grid[Pos::new(row, start)]);
+                if col == self.grid.cols() || self.palette.color(&self.grid[Pos::new(row, col)]) != color {
+                    rect(&mut svg, Pos::new(row, start), col - start, color);
+                    start = col;
+                }
+            }
+        }
+
+        let stroke = self.stroke_width();
+        for overlay in &self.overlays {
+            match overlay {
+                Overlay::Highlight { cells, color } => {
+                    for pos in cells {
+                        rect(&mut svg, *pos, 1, *color);
+                    }
+                }
+                Overlay::Path { cells, color } => {
+                    let points = cells
+                        .iter()
+                        .map(|pos| format!("{},{}", pos.col * size + size / 2, pos.row * size + size / 2))
+                        .collect::<Vec<_>>();
+                    let _ = writeln!(
+                        svg,
+                        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" \
+                         stroke-linecap=\"square\" stroke-linejoin=\"round\"/>",
+                        points.join(" "),
+                        color,
+                        stroke
+                    );
+                }
+                Overlay::Outline { cells, color } => {
+                    // Strokes are centered on the edges, inset them to stay within the cells.
+                    let inset = stroke as f64 / 2.0;
+                    let mut path = String::new();
+                    for (pos, edge) in outline_edges(cells) {
+                        let (left, top) = ((pos.col * size) as f64 + inset, (pos.row * size) as f64 + inset);
+                        let (right, bottom) = (left + size as f64 - stroke as f64, top + size as f64 - stroke as f64);
+                        let ((x1, y1), (x2, y2)) = match edge {
+                            Edge::Top => ((left, top), (right, top)),
+                            Edge::Right => ((right, top), (right, bottom)),
+                            Edge::Bottom => ((left, bottom), (right, bottom)),
+                            Edge::Left => ((left, top), (left, bottom)),
+                        };
+                        let _ = write!(path, "M{} {}L{} {}", x1, y1, x2, y2);
+                    }
+                    let _ = writeln!(
+                        svg,
+                        "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"square\"/>",
+                        path, color, stroke
+                    );
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the image to `path` in the format given by its extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AocError> {
+        let path = path.as_ref();
+        let write_error = |source: io::Error| AocError::Write { path: path.to_path_buf(), source };
+        let bytes = match ImageFormat::from_path(path) {
+            Some(ImageFormat::Ppm) => self.to_ppm(),
+            Some(ImageFormat::Png) => self.to_png().map_err(write_error)?,
+            Some(ImageFormat::Svg) => self.to_svg().into_bytes(),
+            None => {
+                return Err(write_error(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Unknown image format, expected a .ppm, .png or .svg file",
+                )))
+            }
+        };
+        fs::write(path, bytes).map_err(write_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Color, Image, Overlay, Palette};
+    use crate::grid::{Grid, Pos};
+    use crate::AocError;
+
+    const RED: Color = Color::rgb(255, 0, 0);
+
+    fn image(grid: &Grid<char>) -> Image<'_, char> {
+        Image::new(grid, Palette::new(Color::WHITE).with('#', Color::BLACK)).cell_size(4)
+    }
+
+    #[test]
+    fn test_color() {
+        assert_eq!(Color::from_str("#ff8000").unwrap(), Color::rgb(255, 128, 0));
+        assert_eq!(Color::rgb(255, 128, 0).to_string(), "#ff8000");
+        assert!(Color::from_str("ff8000").is_err());
+        assert!(Color::from_str("#ff80zz").is_err());
+    }
+
+    #[test]
+    fn test_ppm() {
+        let grid = Grid::parse("#..\n...\n", |_, c| Ok(c)).unwrap();
+        let ppm = image(&grid)
+            .overlay(Overlay::Highlight { cells: vec![Pos::new(1, 2)], color: RED })
+            .overlay(Overlay::Outline { cells: vec![Pos::new(0, 1), Pos::new(0, 2)], color: RED })
+            .to_ppm();
+
+        let header = b"P6\n12 8\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixel = |x: usize, y: usize| {
+            let index = header.len() + (y * 12 + x) * 3;
+            Color::rgb(ppm[index], ppm[index + 1], ppm[index + 2])
+        };
+        assert_eq!(ppm.len(), header.len() + 12 * 8 * 3);
+        assert_eq!(pixel(0, 0), Color::BLACK);
+        assert_eq!(pixel(1, 5), Color::WHITE);
+        assert_eq!(pixel(10, 6), RED);
+        // The outline covers the border of both cells but not the edge between them.
+        assert_eq!(pixel(4, 1), RED);
+        assert_eq!(pixel(7, 1), Color::WHITE);
+        assert_eq!(pixel(8, 1), Color::WHITE);
+        assert_eq!(pixel(6, 0), RED);
+    }
+
+    #[test]
+    fn test_svg_and_png() {
+        let grid = Grid::parse("#..\n...\n", |_, c| Ok(c)).unwrap();
+        let image = image(&grid).overlay(Overlay::Path { cells: vec![Pos::new(0, 0), Pos::new(1, 2)], color: RED });
+
+        let svg = image.to_svg();
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"4\" height=\"4\" fill=\"#000000\"/>"));
+        assert!(svg.contains("<rect x=\"4\" y=\"0\" width=\"8\" height=\"4\" fill=\"#ffffff\"/>"));
+        assert!(svg.contains("<polyline points=\"2,2 10,6\""));
+        assert!(image.to_png().unwrap().starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_save_unknown_format() {
+        let grid = Grid::parse("#.\n", |_, c| Ok(c)).unwrap();
+        let err = image(&grid).save("image.bmp").unwrap_err();
+        assert!(matches!(err, AocError::Write { .. }));
+        assert_eq!(err.exit_code(), 9);
+    }
+}
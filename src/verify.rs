@@ -0,0 +1,232 @@
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::registry::{self, DynSolution};
+use crate::{parse_input_file, AocError, InputSource, ParseError};
+
+/// Default location of the answer manifest.
+pub const DEFAULT_MANIFEST: &str = "problems/answers.toml";
+
+/// Expected answer of a part, either a number or free text.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// A puzzle input together with its known answers.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct ManifestEntry {
+    pub day: u8,
+    pub path: PathBuf,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl ManifestEntry {
+    pub fn expected(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// List of known inputs and answers, usually read from `problems/answers.toml`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "input", default)]
+    pub inputs: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Loads the manifest at `path`, input paths are resolved relative to its directory.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let manifest_string = parse_input_file(path.as_ref())?;
+        let base_dir = path.as_ref().parent().unwrap_or(Path::new(""));
+        Self::from_toml(&manifest_string, base_dir)
+    }
+
+    pub fn from_toml(manifest_string: &str, base_dir: &Path) -> Result<Self, AocError> {
+        let mut manifest: Manifest = toml::from_str(manifest_string).map_err(|err| {
+            let offset = err.span().map(|span| span.start).unwrap_or(0);
+            ParseError::at_offset(manifest_string, offset, err.message())
+        })?;
+        for entry in manifest.inputs.iter_mut() {
+            entry.path = base_dir.join(&entry.path);
+        }
+        Ok(manifest)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    /// No expected answer is recorded, `actual` is the computed answer if there is an input.
+    Missing { actual: Option<String> },
+    Error(String),
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { .. } => write!(f, "FAIL"),
+            Status::Missing { .. } => write!(f, "missing"),
+            Status::Error(_) => write!(f, "ERROR"),
+        }
+    }
+}
+
+/// Outcome of checking one part of one day against one input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub status: Status,
+}
+
+impl Check {
+    /// Additional information for failed, missing and erroneous checks.
+    pub fn details(&self) -> String {
+        match &self.status {
+            Status::Pass => String::new(),
+            Status::Fail { expected, actual } => format!("expected {}, got {}", expected, actual),
+            Status::Missing { actual: Some(actual) } => format!("got {}", actual),
+            Status::Missing { actual: None } => "no input".to_string(),
+            Status::Error(reason) => reason.clone(),
+        }
+    }
+}
+
+/// Solves `part`, turning a panicking solution into an error so that verification continues.
+fn solve_guarded(solution: &dyn DynSolution, input: &dyn std::any::Any, part: u8) -> Result<String, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| solution.solve(input, part))) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(format!("panicked: {}", message))
+        }
+    }
+}
+
+fn verify_entry(entry: &ManifestEntry) -> Vec<Check> {
+    let input = entry.path.display().to_string();
+    let check = |part: u8, status: Status| Check { day: entry.day, part, input: input.clone(), status };
+
+    let Some(solution) = registry::find(entry.day) else {
+        return (1..=2)
+            .map(|part| check(part, Status::Error("no solution registered".to_string())))
+            .collect();
+    };
+
+    let parsed = InputSource::Path(entry.path.clone())
+        .read(entry.day)
+        .and_then(|input_string| solution.parse(&input_string));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return (1..=2)
+                .map(|part| check(part, Status::Error(err.to_string())))
+                .collect();
+        }
+    };
+
+    (1..=2)
+        .map(|part| {
+            let status = match (solve_guarded(solution, parsed.as_ref(), part), entry.expected(part)) {
+                (Err(reason), _) => Status::Error(reason),
+                (Ok(actual), None) => Status::Missing { actual: Some(actual) },
+                (Ok(actual), Some(expected)) if actual == expected.to_string() => Status::Pass,
+                (Ok(actual), Some(expected)) => Status::Fail { expected: expected.to_string(), actual },
+            };
+            check(part, status)
+        })
+        .collect()
+}
+
+/// Runs every registered solution against every input of `manifest`.
+///
+/// Registered days without any input in the manifest are reported as missing.
+pub fn verify(manifest: &Manifest) -> Vec<Check> {
+    let mut checks = manifest.inputs.iter().flat_map(verify_entry).collect::<Vec<_>>();
+
+    for solution in registry::solutions() {
+        if manifest.inputs.iter().all(|entry| entry.day != solution.day()) {
+            checks.extend((1..=2).map(|part| Check {
+                day: solution.day(),
+                part,
+                input: "-".to_string(),
+                status: Status::Missing { actual: None },
+            }));
+        }
+    }
+
+    checks.sort_by_key(|check| check.day);
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{verify, Manifest, Status, DEFAULT_MANIFEST};
+
+    #[test]
+    fn test_manifest_inputs_exist() {
+        let manifest = Manifest::load(DEFAULT_MANIFEST).unwrap();
+
+        assert!(manifest.inputs.iter().all(|entry| entry.path.exists()));
+    }
+
+    #[test]
+    fn test_verify() {
+        let manifest = Manifest::from_toml(
+            "[[input]]\nday = 2\npath = \"problem2_test.txt\"\npart1 = 2\npart2 = 5\n",
+            Path::new("problems"),
+        )
+        .unwrap();
+
+        let checks = verify(&manifest);
+        let day2 = checks.iter().filter(|check| check.day == 2).collect::<Vec<_>>();
+        assert_eq!(day2[0].status, Status::Pass);
+        assert_eq!(day2[1].status, Status::Fail { expected: "5".to_string(), actual: "4".to_string() });
+        assert!(checks
+            .iter()
+            .filter(|check| check.day == 1)
+            .all(|check| check.status == Status::Missing { actual: None }));
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        let err = Manifest::from_toml("[[input]]\nday = \"one\"\n", Path::new("")).unwrap_err();
+        assert_eq!(err.exit_code(), 6);
+    }
+}
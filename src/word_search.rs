@@ -0,0 +1,422 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aho_corasick::AhoCorasick;
+
+use crate::grid::{Direction, Grid, Pos};
+
+/// Algorithm used to find the words.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum SearchMode {
+    /// Walks every direction from every cell starting with the first character of a word.
+    #[default]
+    Scan,
+    /// Extracts every row, column and diagonal as a string and searches all words at once
+    /// with an Aho-Corasick automaton. Pays off for many words, a single word is found about
+    /// as fast as by the scan, see `examples/word_search_bench.rs`.
+    Automaton,
+}
+
+/// Directions words may run in and whether they may wrap around the grid.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SearchConfig {
+    /// Left to right and top to bottom.
+    pub straight: bool,
+    /// Top left to bottom right and top right to bottom left.
+    pub diagonal: bool,
+    /// Also search the enabled directions backwards, e.g. right to left.
+    pub reversed: bool,
+    /// Words leaving the grid continue on the opposite side.
+    pub wrap: bool,
+    pub mode: SearchMode,
+}
+
+impl Default for SearchConfig {
+    /// All eight directions without wrapping, as in a classic word search.
+    fn default() -> Self {
+        SearchConfig {
+            straight: true,
+            diagonal: true,
+            reversed: true,
+            wrap: false,
+            mode: SearchMode::Scan,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// Enabled directions without the reversed ones.
+    fn forward_directions(&self) -> Vec<Direction> {
+        let mut directions = Vec::with_capacity(4);
+        if self.straight {
+            directions.extend([Direction::Right, Direction::Down]);
+        }
+        if self.diagonal {
+            directions.extend([Direction::DownRight, Direction::DownLeft]);
+        }
+        directions
+    }
+
+    /// Directions enabled by this configuration.
+    pub fn directions(&self) -> Vec<Direction> {
+        let mut directions = self.forward_directions();
+        if self.reversed {
+            let forward = directions.len();
+            for index in 0..forward {
+                directions.push(directions[index].opposite());
+            }
+        }
+        directions
+    }
+}
+
+/// Occurrence of a word in a grid.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct WordMatch {
+    pub word: String,
+    pub start: Pos,
+    pub direction: Direction,
+    /// Position of every character of the word, starting with `start`.
+    pub positions: Vec<Pos>,
+}
+
+/// Automaton over all words and their reversals used by [`SearchMode::Automaton`].
+#[derive(Debug, Clone)]
+struct Automaton {
+    automaton: AhoCorasick,
+    /// Words and whether they are reversed for every pattern of the automaton.
+    patterns: Vec<Vec<(usize, bool)>>,
+    forward_directions: Vec<Direction>,
+}
+
+impl Automaton {
+    fn new(words: &[Vec<char>], config: &SearchConfig) -> Self {
+        let mut pattern_indices: HashMap<String, usize> = HashMap::new();
+        let mut pattern_strings = Vec::new();
+        let mut patterns: Vec<Vec<(usize, bool)>> = Vec::new();
+        for (word_index, word) in words.iter().enumerate() {
+            let mut variants = vec![(word.iter().collect::<String>(), false)];
+            if config.reversed {
+                variants.push((word.iter().rev().collect::<String>(), true));
+            }
+            for (pattern, reversed) in variants {
+                let index = *pattern_indices.entry(pattern.clone()).or_insert_with(|| {
+                    pattern_strings.push(pattern);
+                    patterns.push(Vec::new());
+                    patterns.len() - 1
+                });
+                patterns[index].push((word_index, reversed));
+            }
+        }
+
+        Automaton {
+            automaton: AhoCorasick::new(&pattern_strings).expect("Word patterns are always valid"),
+            patterns,
+            forward_directions: config.forward_directions(),
+        }
+    }
+}
+
+/// Line of cells through a grid, `len` cells in a direction starting at `start`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Line {
+    start: Pos,
+    len: usize,
+    /// Whether the line is a cycle through the wrapping grid.
+    cyclic: bool,
+}
+
+impl Line {
+    /// Position of the cell `index` steps along the line, cycles repeat after `len` steps.
+    fn pos<T>(&self, grid: &Grid<T>, direction: Direction, index: usize) -> Pos {
+        grid.offset_wrapping(self.start, direction.delta() * (index % self.len) as isize)
+    }
+
+    /// Positions along the line, continuing around cycles.
+    fn positions<'a, T>(&self, grid: &'a Grid<T>, direction: Direction) -> impl Iterator<Item = Pos> + 'a {
+        let cyclic = self.cyclic;
+        std::iter::successors(Some(self.start), move |pos| {
+            if cyclic {
+                Some(grid.offset_wrapping(*pos, direction))
+            } else {
+                grid.offset(*pos, direction)
+            }
+        })
+    }
+}
+
+/// Every line of `grid` in `direction`.
+///
+/// Without wrapping, a line starts at the border and ends at the opposite border. With wrapping,
+/// every cell lies on exactly one cycle, which starts at its first cell in row-major order.
+fn grid_lines<T>(grid: &Grid<T>, direction: Direction, wrap: bool) -> Vec<Line> {
+    let mut lines = Vec::new();
+    if wrap {
+        let mut visited = Grid::from_elem(grid.rows(), grid.cols(), false);
+        for (start, _) in grid.iter() {
+            if visited[start] {
+                continue;
+            }
+            let mut len = 0;
+            let mut pos = start;
+            while !visited[pos] {
+                visited[pos] = true;
+                len += 1;
+                pos = grid.offset_wrapping(pos, direction);
+            }
+            lines.push(Line { start, len, cyclic: true });
+        }
+    } else {
+        let (last_row, last_col) = (grid.rows() - 1, grid.cols() - 1);
+        let border = (0..grid.cols())
+            .flat_map(|col| [Pos::new(0, col), Pos::new(last_row, col)])
+            .chain((0..grid.rows()).flat_map(|row| [Pos::new(row, 0), Pos::new(row, last_col)]))
+            .collect::<BTreeSet<_>>();
+        // Cells left in the grid from a coordinate moving by `delta` along an axis of length `len`.
+        let steps = |value: usize, delta: isize, len: usize| match delta {
+            1 => len - value,
+            -1 => value + 1,
+            _ => usize::MAX,
+        };
+        let (delta, previous) = (direction.delta(), direction.opposite());
+        for start in border.into_iter().filter(|pos| grid.offset(*pos, previous).is_none()) {
+            let len = steps(start.row, delta.d_row, grid.rows()).min(steps(start.col, delta.d_col, grid.cols()));
+            lines.push(Line { start, len, cyclic: false });
+        }
+    }
+    lines
+}
+
+/// Finds a list of words in character grids.
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    words: Vec<Vec<char>>,
+    /// Indices of the words starting with each character.
+    by_first_char: HashMap<char, Vec<usize>>,
+    directions: Vec<Direction>,
+    wrap: bool,
+    automaton: Option<Automaton>,
+}
+
+impl WordSearch {
+    /// Creates a search for `words`, empty words are ignored.
+    pub fn new<S: AsRef<str>>(words: impl IntoIterator<Item = S>, config: SearchConfig) -> Self {
+        let words = words
+            .into_iter()
+            .map(|word| word.as_ref().chars().collect::<Vec<_>>())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+
+        let mut by_first_char: HashMap<char, Vec<usize>> = HashMap::new();
+        for (index, word) in words.iter().enumerate() {
+            by_first_char.entry(word[0]).or_default().push(index);
+        }
+
+        let automaton = match config.mode {
+            SearchMode::Scan => None,
+            SearchMode::Automaton => Some(Automaton::new(&words, &config)),
+        };
+
+        WordSearch {
+            words,
+            by_first_char,
+            directions: config.directions(),
+            wrap: config.wrap,
+            automaton,
+        }
+    }
+
+    /// Positions of `word` from `start` in `direction`, `None` if it does not occur there.
+    fn match_at(&self, grid: &Grid<char>, word: &[char], start: Pos, direction: Direction) -> Option<Vec<Pos>> {
+        let mut positions = Vec::with_capacity(word.len());
+        positions.push(start);
+        let mut pos = start;
+        for expected in &word[1..] {
+            pos = if self.wrap {
+                Some(grid.offset_wrapping(pos, direction)).filter(|next| *next != start)?
+            } else {
+                grid.offset(pos, direction)?
+            };
+            if grid[pos] != *expected {
+                return None;
+            }
+            positions.push(pos);
+        }
+        Some(positions)
+    }
+
+    /// Every occurrence of every word in `grid`, ordered by start position, word and direction.
+    ///
+    /// Words reading the same in several directions, like palindromes with `reversed` set,
+    /// are reported once per direction. Both modes return the same matches.
+    pub fn find(&self, grid: &Grid<char>) -> Vec<WordMatch> {
+        match &self.automaton {
+            Some(automaton) => self.find_automaton(grid, automaton),
+            None => self.find_scan(grid),
+        }
+    }
+
+    fn find_automaton(&self, grid: &Grid<char>, automaton: &Automaton) -> Vec<WordMatch> {
+        let longest_word = self.words.iter().map(Vec::len).max().unwrap_or(0);
+        let mut matches = Vec::new();
+        for &direction in &automaton.forward_directions {
+            let lines = grid_lines(grid, direction, self.wrap);
+
+            // All lines of a direction are searched at once, separated by newlines which never
+            // occur in a grid. Cycles are extended so that words crossing their end are found too.
+            let mut text = String::with_capacity(grid.rows() * grid.cols() + lines.len());
+            let mut line_starts = Vec::with_capacity(lines.len());
+            let mut chars = 0;
+            for line in &lines {
+                let extension = if line.cyclic { longest_word.saturating_sub(1).min(line.len) } else { 0 };
+                line_starts.push(chars);
+                text.extend(line.positions(grid, direction).take(line.len + extension).map(|pos| grid[pos]));
+                text.push('\n');
+                chars += line.len + extension + 1;
+            }
+            let char_starts = if text.is_ascii() {
+                None
+            } else {
+                Some(text.char_indices().map(|(index, _)| index).collect::<Vec<_>>())
+            };
+
+            for hit in automaton.automaton.find_overlapping_iter(&text) {
+                let start = match &char_starts {
+                    None => hit.start(),
+                    Some(char_starts) => char_starts.binary_search(&hit.start()).expect("Hits start at a character"),
+                };
+                let line_index = line_starts.partition_point(|line_start| *line_start <= start) - 1;
+                let line = &lines[line_index];
+                let first = start - line_starts[line_index];
+                let line_end = line_starts.get(line_index + 1).map(|next| next - 1).unwrap_or(chars - 1);
+                if first >= line.len {
+                    continue;
+                }
+
+                for &(word_index, reversed) in &automaton.patterns[hit.pattern().as_usize()] {
+                    let word = &self.words[word_index];
+                    if word.len() > line.len || start + word.len() > line_end {
+                        continue;
+                    }
+                    let mut positions = (first..first + word.len())
+                        .map(|index| line.pos(grid, direction, index))
+                        .collect::<Vec<_>>();
+                    let word_direction = if reversed {
+                        positions.reverse();
+                        direction.opposite()
+                    } else {
+                        direction
+                    };
+                    let found = WordMatch {
+                        word: word.iter().collect(),
+                        start: positions[0],
+                        direction: word_direction,
+                        positions,
+                    };
+                    matches.push((word_index, found));
+                }
+            }
+        }
+
+        let direction_order = |direction: Direction| self.directions.iter().position(|d| *d == direction);
+        matches.sort_by_cached_key(|(word_index, found)| (found.start, *word_index, direction_order(found.direction)));
+        matches.into_iter().map(|(_, found)| found).collect()
+    }
+
+    fn find_scan(&self, grid: &Grid<char>) -> Vec<WordMatch> {
+        let mut matches = Vec::new();
+        for (start, c) in grid.iter() {
+            let Some(word_indices) = self.by_first_char.get(c) else {
+                continue;
+            };
+            for &word_index in word_indices {
+                let word = &self.words[word_index];
+                for &direction in &self.directions {
+                    if let Some(positions) = self.match_at(grid, word, start, direction) {
+                        matches.push(WordMatch {
+                            word: word.iter().collect(),
+                            start,
+                            direction,
+                            positions,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchConfig, SearchMode, WordSearch};
+    use crate::grid::{Direction, Grid, Pos};
+
+    fn grid(input_string: &str) -> Grid<char> {
+        Grid::parse(input_string, |_, c| Ok(c)).unwrap()
+    }
+
+    #[test]
+    fn test_find() {
+        let search_grid = grid(include_str!("../problems/problem4_test.txt"));
+
+        let all = WordSearch::new(["XMAS"], SearchConfig::default()).find(&search_grid);
+        assert_eq!(all.len(), 18);
+
+        let forward = SearchConfig { reversed: false, ..SearchConfig::default() };
+        let forward_matches = WordSearch::new(["XMAS"], forward).find(&search_grid);
+        let backward_matches = WordSearch::new(["SAMX"], forward).find(&search_grid);
+        assert_eq!(forward_matches.len() + backward_matches.len(), 18);
+    }
+
+    #[test]
+    fn test_match_details() {
+        let search_grid = grid("CAT.\n.O..\n..W.\nDOG.\n");
+        let config = SearchConfig { diagonal: true, straight: true, reversed: true, ..SearchConfig::default() };
+        let matches = WordSearch::new(["cat", "COW", "GOD", ""], config).find(&search_grid);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].word, "COW");
+        assert_eq!(matches[0].direction, Direction::DownRight);
+        assert_eq!(matches[0].positions, [Pos::new(0, 0), Pos::new(1, 1), Pos::new(2, 2)]);
+        assert_eq!(matches[1].word, "GOD");
+        assert_eq!(matches[1].start, Pos::new(3, 2));
+        assert_eq!(matches[1].direction, Direction::Left);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let search_grid = grid("TAC\n...\n");
+        let wrapping = SearchConfig { wrap: true, ..SearchConfig::default() };
+
+        assert!(WordSearch::new(["CAT"], SearchConfig::default()).find(&search_grid).len() == 1);
+        let matches = WordSearch::new(["CTA"], wrapping).find(&search_grid);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].positions, [Pos::new(0, 2), Pos::new(0, 0), Pos::new(0, 1)]);
+        // Wrapping never reuses a cell within one word.
+        assert!(WordSearch::new(["CTAC"], wrapping).find(&search_grid).is_empty());
+    }
+
+    #[test]
+    fn test_automaton_matches_scan() {
+        let grids = [
+            grid(include_str!("../problems/problem4_test.txt")),
+            grid("ABAB\nBABA\nABBA\n"),
+            grid("TAC\n...\n"),
+        ];
+        let words = ["XMAS", "SAM", "A", "ABA", "BAB", "ABBA", "CTA", "ABABA"];
+        for search_grid in &grids {
+            let flags = [true, false];
+            for (straight, diagonal, reversed, wrap) in itertools::iproduct!(flags, flags, flags, flags) {
+                let config = SearchConfig { straight, diagonal, reversed, wrap, mode: SearchMode::Scan };
+                let automaton = SearchConfig { mode: SearchMode::Automaton, ..config };
+
+                assert_eq!(
+                    WordSearch::new(words, automaton).find(search_grid),
+                    WordSearch::new(words, config).find(search_grid),
+                    "{:?}",
+                    config
+                );
+            }
+        }
+    }
+}